@@ -9,9 +9,9 @@ use std::hint::black_box;
 
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use digraphx_rs::graph_from_edges;
 use digraphx_rs::neg_cycle::NegCycleFinder;
-use digraphx_rs::parametric::{MaxParametricSolver, ParametricAPI};
+use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle, ParametricAPI};
+use digraphx_rs::{graph_from_edges, graph_from_edges_array, min_mean_cycle, Graph};
 use num::rational::Ratio;
 
 // ---------------------------------------------------------------------------
@@ -59,6 +59,111 @@ fn bench_neg_cycle_medium(c: &mut Criterion) {
     });
 }
 
+// ---------------------------------------------------------------------------
+// Neighbor iteration on larger graphs
+// ---------------------------------------------------------------------------
+//
+// `Graph::neighbors` used to collect every adjacency list into a fresh `Vec`;
+// it now returns an iterator borrowing the graph.  Criterion, 1000 nodes and
+// ~8000 edges, before -> after:
+//
+//   neighbors_scan_hashmap   97.5 µs -> 50.9 µs   (-49%)
+//   neighbors_scan_vec       29.5 µs ->  6.8 µs   (-78%)
+//
+// The end-to-end `howard_large_*` runs changed within noise: their cost is
+// dominated by the `HashMap` lookups into `dist` and `pred`.
+//
+// With `dist` and `pred` behind `NodeMap`, index-based graphs keep `pred` in
+// a `VecMap` and callers may pass a plain `Vec` for `dist`.  Same run:
+//
+//   howard_large_hashmap     4.40 ms   (HashMap graph, HashMap dist)
+//   howard_large_vec         2.16 ms   (Vec graph, HashMap dist)
+//   howard_large_vec_dense   0.23 ms   (Vec graph, Vec dist)
+
+/// Edges of a 1000-node graph with 8 out-edges per node.  The weights are
+/// non-negative costs shifted by node potentials, so there are many negative
+/// edges but no negative cycle and `howard` relaxes until convergence.
+fn large_edges() -> Vec<(usize, usize, f64)> {
+    shifted_edges(1000, 0)
+}
+
+/// Edges of an `n`-node graph built as in [`large_edges`], with the arcs,
+/// costs and potentials shifted by `shift`.
+fn shifted_edges(n: usize, shift: usize) -> Vec<(usize, usize, f64)> {
+    let potential = |x: usize| ((x * 37 + shift) % 101) as f64;
+    let mut edges = Vec::new();
+    for u in 0..n {
+        for k in 1..=8 {
+            let v = (u * 7 + k * 131 + shift) % n;
+            if v != u {
+                let cost = ((u + v + shift) % 17) as f64;
+                edges.push((u, v, cost + potential(v) - potential(u)));
+            }
+        }
+    }
+    edges
+}
+
+/// One full sweep over every adjacency list, as done by each `relax` pass.
+fn scan_edges<G: Graph<Edge = f64>>(graph: &G) -> f64 {
+    let mut total = 0.0;
+    for u in graph.nodes() {
+        for (_, w) in graph.neighbors(u) {
+            total += w;
+        }
+    }
+    total
+}
+
+fn bench_neighbors_scan(c: &mut Criterion) {
+    let edges = large_edges();
+    let hashmap = graph_from_edges(&edges);
+    let vec = graph_from_edges_array(&edges);
+
+    c.bench_function("neighbors_scan_hashmap", |b| {
+        b.iter(|| scan_edges(black_box(&hashmap)))
+    });
+    c.bench_function("neighbors_scan_vec", |b| {
+        b.iter(|| scan_edges(black_box(&vec)))
+    });
+}
+
+fn bench_howard_large_hashmap(c: &mut Criterion) {
+    let graph = graph_from_edges(&large_edges());
+
+    c.bench_function("howard_large_hashmap", |b| {
+        b.iter(|| {
+            let mut ncf = NegCycleFinder::new(black_box(&graph));
+            let mut dist: HashMap<usize, f64> = HashMap::new();
+            ncf.howard(&mut dist, |w| *w)
+        })
+    });
+}
+
+fn bench_howard_large_vec(c: &mut Criterion) {
+    let graph = graph_from_edges_array(&large_edges());
+
+    c.bench_function("howard_large_vec", |b| {
+        b.iter(|| {
+            let mut ncf = NegCycleFinder::new(black_box(&graph));
+            let mut dist: HashMap<usize, f64> = HashMap::new();
+            ncf.howard(&mut dist, |w| *w)
+        })
+    });
+}
+
+fn bench_howard_large_vec_dense(c: &mut Criterion) {
+    let graph = graph_from_edges_array(&large_edges());
+
+    c.bench_function("howard_large_vec_dense", |b| {
+        b.iter(|| {
+            let mut ncf = NegCycleFinder::new(black_box(&graph));
+            let mut dist = vec![0.0; graph.len()];
+            ncf.howard(&mut dist, |w| *w)
+        })
+    });
+}
+
 // ---------------------------------------------------------------------------
 // Howard's algorithm with Ratio weights
 // ---------------------------------------------------------------------------
@@ -119,7 +224,7 @@ fn bench_parametric_solver(c: &mut Criterion) {
     c.bench_function("parametric_solver", |b| {
         b.iter(|| {
             let mut solver = MaxParametricSolver::new(black_box(&graph), MyRatio);
-            let mut dist: HashMap<u32, Ratio<i32>> = [
+            let dist: HashMap<u32, Ratio<i32>> = [
                 (0, Ratio::new(0, 1)),
                 (1, Ratio::new(0, 1)),
                 (2, Ratio::new(0, 1)),
@@ -129,18 +234,79 @@ fn bench_parametric_solver(c: &mut Criterion) {
                 (6, Ratio::new(0, 1)),
             ]
             .into();
-            let mut ratio = Ratio::new(1_000_000, 1);
-            solver.run(&mut dist, &mut ratio)
+            solver.run(dist, Ratio::new(1_000_000, 1))
         })
     });
 }
 
+// ---------------------------------------------------------------------------
+// Parametric solver on large graphs: cold vs warm start
+// ---------------------------------------------------------------------------
+//
+// Minimum mean cycle starting from a ratio of 1000, on the `large_edges`
+// graph and on a 10000-node graph whose rounds need many more passes.
+// Criterion:
+//
+//   parametric_1000_cold     0.51 ms   (4 rounds, 11 passes)
+//   parametric_1000_warm     0.58 ms   (4 rounds, 12 passes)
+//   parametric_10000_cold    37.2 ms   (10 rounds, 88 passes)
+//   parametric_10000_warm    18.0 ms   (6 rounds, 39 passes)
+//
+// A warm round resumes from the potentials of the previous one, and takes
+// the best cycle of its final predecessor map so that the ratio still drops
+// in large steps.  This pays off when cold rounds need many passes; when
+// they converge in a few, the extra cycle scan makes warm starts slightly
+// slower.  Warm starts are therefore opt-in.
+
+fn bench_parametric_large(c: &mut Criterion) {
+    for (n, shift) in [(1000, 0), (10_000, 1)] {
+        let graph = graph_from_edges_array(&shifted_edges(n, shift));
+        c.bench_function(&format!("parametric_{n}_cold"), |b| {
+            b.iter(|| {
+                let mut solver = MaxParametricSolver::new(black_box(&graph), MinMeanCycle);
+                solver.run(vec![0.0; n], 1000.0)
+            })
+        });
+        c.bench_function(&format!("parametric_{n}_warm"), |b| {
+            b.iter(|| {
+                let mut solver =
+                    MaxParametricSolver::new(black_box(&graph), MinMeanCycle).with_warm_start(true);
+                solver.run(vec![0.0; n], 1000.0)
+            })
+        });
+    }
+}
+
+fn bench_karp(c: &mut Criterion) {
+    // Same graph as `parametric_solver`
+    let graph = graph_from_edges(&[
+        (0u32, 1, Ratio::new(1, 1)),
+        (1, 2, Ratio::new(1, 1)),
+        (2, 0, Ratio::new(1, 1)),
+        (2, 3, Ratio::new(2, 1)),
+        (3, 4, Ratio::new(3, 1)),
+        (4, 5, Ratio::new(1, 1)),
+        (5, 6, Ratio::new(1, 1)),
+        (6, 0, Ratio::new(2, 1)),
+    ]);
+
+    c.bench_function("karp_min_mean_cycle", |b| {
+        b.iter(|| min_mean_cycle(black_box(&graph), |w: &Ratio<i32>| *w))
+    });
+}
+
 criterion_group!(
     benches,
     bench_neg_cycle_small,
     bench_neg_cycle_no_cycle,
     bench_neg_cycle_medium,
+    bench_neighbors_scan,
+    bench_howard_large_hashmap,
+    bench_howard_large_vec,
+    bench_howard_large_vec_dense,
     bench_howard_ratio,
-    bench_parametric_solver
+    bench_parametric_solver,
+    bench_parametric_large,
+    bench_karp
 );
 criterion_main!(benches);
@@ -1,35 +1,31 @@
 use digraphx_rs::graph_from_edges;
-use digraphx_rs::parametric::{MaxParametricSolver, ParametricAPI};
-/// Example: minimum cycle ratio using the generic container-of-containers API.
+use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+/// Example: minimum mean cycle using the generic container-of-containers API.
+use num::rational::Ratio;
 use std::collections::HashMap;
 
-struct MinCycleRatio;
-
-impl ParametricAPI<i32> for MinCycleRatio {
-    fn distance(&self, r: &i32, w: &i32) -> i32 {
-        *w - *r
-    }
-    fn zero_cancel(&self, cycle: &[i32]) -> i32 {
-        cycle.iter().sum::<i32>() / cycle.len() as i32
-    }
-}
-
 fn main() {
-    // Build a graph using the convenient helper.
+    // Build a graph using the convenient helper; exact rational weights keep
+    // the ratio from being truncated.
+    let w = |x: i64| Ratio::from_integer(x);
     let graph = graph_from_edges(&[
-        (0, 1, 5),
-        (0, 2, 1),
-        (1, 0, 1),
-        (1, 2, 1),
-        (2, 1, 1),
-        (2, 0, 1),
+        (0, 1, w(5)),
+        (0, 2, w(1)),
+        (1, 0, w(2)),
+        (1, 2, w(2)),
+        (2, 1, w(1)),
+        (2, 0, w(2)),
     ]);
 
-    let mut solver = MaxParametricSolver::new(&graph, MinCycleRatio);
-    let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
-    let mut ratio = 100;
-    solver.run(&mut dist, &mut ratio);
+    let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+    let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+    let result = solver.run(dist, w(100));
+    let ratio = result.ratio;
+    let cycle = result.cycle;
 
-    println!("Minimum cycle ratio: {ratio}");
-    assert_eq!(ratio, 1);
+    println!("Minimum mean cycle: {ratio}");
+    if let Some(cycle) = cycle {
+        println!("Critical cycle: {:?}", cycle.nodes);
+    }
+    assert_eq!(ratio, Ratio::new(4, 3));
 }
@@ -0,0 +1,101 @@
+//! Comparison policies for distances.
+//!
+//! The finders decide whether an arc improves a distance, and the parametric
+//! solvers whether a cycle improves the ratio, through a [`Compare`] policy.
+//! [`Exact`] is the plain `<` used by default and is right for integers and
+//! `Ratio`.  With floating-point weights rounding noise can make a
+//! zero-weight cycle look negative by a few ulps; [`Tolerance`] only counts a
+//! value as smaller when it is smaller by more than an absolute and a
+//! relative margin.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use digraphx_rs::compare::Tolerance;
+//! use digraphx_rs::{graph_from_edges, NegCycleFinder};
+//!
+//! // A zero-weight cycle, up to rounding
+//! let graph = graph_from_edges(&[(0, 1, 0.3), (1, 2, -0.1), (2, 0, -0.2)]);
+//! let mut dist: HashMap<i32, f64> = HashMap::new();
+//! assert!(NegCycleFinder::new(&graph).howard(&mut dist, |w| *w).is_some());
+//!
+//! let mut ncf = NegCycleFinder::new(&graph).with_compare(Tolerance::new(1e-12, 1e-12));
+//! let mut dist: HashMap<i32, f64> = HashMap::new();
+//! assert!(ncf.howard(&mut dist, |w| *w).is_none());
+//! ```
+
+use num::Float;
+
+/// Strict "less than" on distances.
+pub trait Compare<D> {
+    /// Return `true` if `a` is smaller than `b` under this policy.
+    fn less(&self, a: &D, b: &D) -> bool;
+}
+
+/// Exact comparison with `<`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Exact;
+
+impl<D: PartialOrd> Compare<D> for Exact {
+    #[inline]
+    fn less(&self, a: &D, b: &D) -> bool {
+        a < b
+    }
+}
+
+/// Comparison with an absolute and a relative tolerance.
+///
+/// `a` is less than `b` when
+///
+/// $$ b - a > \max(\text{abs}, \text{rel} \cdot \max(|a|, |b|)) $$
+///
+/// Differences within the tolerance are treated as equal, so a finder
+/// using this policy only reports cycles whose weight is clearly negative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance<T> {
+    /// Absolute tolerance.
+    pub abs: T,
+    /// Tolerance relative to the larger magnitude of the two operands.
+    pub rel: T,
+}
+
+impl<T> Tolerance<T> {
+    /// Create a policy with the given absolute and relative tolerances.
+    pub fn new(abs: T, rel: T) -> Self {
+        Tolerance { abs, rel }
+    }
+}
+
+impl<T: Float> Compare<T> for Tolerance<T> {
+    #[inline]
+    fn less(&self, a: &T, b: &T) -> bool {
+        let scale = a.abs().max(b.abs());
+        *b - *a > self.abs.max(self.rel * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact() {
+        assert!(Exact.less(&1, &2));
+        assert!(!Exact.less(&2, &2));
+        assert!(Exact.less(&(0.3 - 1e-17), &(0.1 + 0.2)));
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let tol = Tolerance::new(1e-9, 1e-6);
+        assert!(!tol.less(&(0.1 + 0.2), &0.3));
+        assert!(!tol.less(&0.3, &(0.1 + 0.2)));
+        assert!(!tol.less(&0.0, &1e-10));
+        assert!(tol.less(&0.0, &1e-8));
+        // The relative margin dominates for large values
+        assert!(!tol.less(&1e6, &(1e6 + 0.5)));
+        assert!(tol.less(&1e6, &(1e6 + 2.0)));
+        assert!(Tolerance::new(0.0f32, 0.0).less(&1.0, &1.5));
+    }
+}
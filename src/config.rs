@@ -0,0 +1,240 @@
+//! Limits and cancellation for long-running searches.
+//!
+//! Howard's algorithm and the parametric solvers loop until convergence.  A
+//! [`SolverConfig`] bounds such a search by a number of relaxation passes, a
+//! wall-clock deadline and a cancellation flag that another thread may set.
+//! The `*_with_config` entry points check the configuration before every
+//! pass and return an [`Outcome`], which is [`Outcome::Interrupted`] with the
+//! best partial state when a limit was hit.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use digraphx_rs::config::{Interrupt, Outcome, SolverConfig};
+//! use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+//!
+//! let graph: Vec<Vec<(usize, f64)>> = vec![vec![(1, 1.0)], vec![(0, 2.0)]];
+//! let cancel = Arc::new(AtomicBool::new(false));
+//! let config = SolverConfig::new()
+//!     .with_max_passes(1000)
+//!     .with_timeout(Duration::from_secs(10))
+//!     .with_cancel(Arc::clone(&cancel));
+//!
+//! let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+//! let outcome = solver.run_with_config(vec![0.0; 2], 10.0, &config);
+//! assert_eq!(outcome.into_inner().ratio, 1.5);
+//!
+//! // Another thread would set the flag while the search runs
+//! cancel.store(true, Ordering::Relaxed);
+//! match solver.run_with_config(vec![0.0; 2], 10.0, &config) {
+//!     Outcome::Interrupted { reason, partial } => {
+//!         assert_eq!(reason, Interrupt::Cancelled);
+//!         assert_eq!(partial.ratio, 10.0);
+//!     }
+//!     Outcome::Done(_) => unreachable!(),
+//! }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Limits checked before every relaxation pass.
+///
+/// The default configuration sets no limit.
+#[derive(Debug, Clone, Default)]
+pub struct SolverConfig {
+    max_passes: Option<usize>,
+    deadline: Option<Instant>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl SolverConfig {
+    /// Create a configuration without limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop after `max_passes` relaxation passes.  For the parametric
+    /// solvers the passes of all rounds count towards the limit.
+    pub fn with_max_passes(mut self, max_passes: usize) -> Self {
+        self.max_passes = Some(max_passes);
+        self
+    }
+
+    /// Stop once `deadline` has passed.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop once `timeout` has elapsed from now.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Stop as soon as `cancel` is set to `true`.
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Return the maximum number of relaxation passes, if any.
+    pub fn max_passes(&self) -> Option<usize> {
+        self.max_passes
+    }
+
+    /// Return the deadline, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Return the reason to stop before the next pass, given the number of
+    /// passes done so far, or `None` to carry on.
+    pub(crate) fn check(&self, passes: usize) -> Option<Interrupt> {
+        if self.max_passes.is_some_and(|max| passes >= max) {
+            return Some(Interrupt::IterationLimit);
+        }
+        if self
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+        {
+            return Some(Interrupt::Cancelled);
+        }
+        if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            return Some(Interrupt::Deadline);
+        }
+        None
+    }
+}
+
+/// Why a search was interrupted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// The maximum number of relaxation passes was reached.
+    IterationLimit,
+    /// The deadline passed.
+    Deadline,
+    /// The cancellation flag was set.
+    Cancelled,
+}
+
+impl fmt::Display for Interrupt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Interrupt::IterationLimit => write!(f, "iteration limit reached"),
+            Interrupt::Deadline => write!(f, "deadline passed"),
+            Interrupt::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// Result of a search run under a [`SolverConfig`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome<T> {
+    /// The search ran to completion.
+    Done(T),
+    /// The search stopped early; `partial` is the state reached so far.
+    Interrupted {
+        /// The limit that was hit.
+        reason: Interrupt,
+        /// The best partial result.
+        partial: T,
+    },
+}
+
+impl<T> Outcome<T> {
+    /// Return `true` if the search was interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        matches!(self, Outcome::Interrupted { .. })
+    }
+
+    /// Return the reason of the interruption, if any.
+    pub fn interrupt(&self) -> Option<Interrupt> {
+        match self {
+            Outcome::Done(_) => None,
+            Outcome::Interrupted { reason, .. } => Some(*reason),
+        }
+    }
+
+    /// Return the result, complete or partial.
+    pub fn into_inner(self) -> T {
+        match self {
+            Outcome::Done(value) => value,
+            Outcome::Interrupted { partial, .. } => partial,
+        }
+    }
+
+    /// Apply `f` to the result, complete or partial.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Outcome<U> {
+        match self {
+            Outcome::Done(value) => Outcome::Done(f(value)),
+            Outcome::Interrupted { reason, partial } => Outcome::Interrupted {
+                reason,
+                partial: f(partial),
+            },
+        }
+    }
+
+    /// Return the result if the search ran to completion.
+    pub fn done(self) -> Option<T> {
+        match self {
+            Outcome::Done(value) => Some(value),
+            Outcome::Interrupted { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let config = SolverConfig::new();
+        assert_eq!(config.check(usize::MAX), None);
+
+        let config = SolverConfig::new().with_max_passes(3);
+        assert_eq!(config.check(2), None);
+        assert_eq!(config.check(3), Some(Interrupt::IterationLimit));
+
+        let config = SolverConfig::new().with_deadline(Instant::now());
+        assert_eq!(config.check(0), Some(Interrupt::Deadline));
+        let config = SolverConfig::new().with_timeout(Duration::from_secs(3600));
+        assert_eq!(config.check(0), None);
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let config = SolverConfig::new().with_cancel(Arc::clone(&cancel));
+        assert_eq!(config.check(0), None);
+        cancel.store(true, Ordering::Relaxed);
+        assert_eq!(config.check(0), Some(Interrupt::Cancelled));
+    }
+
+    #[test]
+    fn test_outcome() {
+        let done = Outcome::Done(1);
+        assert!(!done.is_interrupted());
+        assert_eq!(done.interrupt(), None);
+        assert_eq!(done.done(), Some(1));
+
+        let stopped = Outcome::Interrupted {
+            reason: Interrupt::Deadline,
+            partial: 2,
+        };
+        assert!(stopped.is_interrupted());
+        assert_eq!(stopped.interrupt(), Some(Interrupt::Deadline));
+        assert_eq!(stopped.clone().into_inner(), 2);
+        assert_eq!(stopped.clone().map(|x| x * 2).into_inner(), 4);
+        assert_eq!(
+            stopped.clone().map(|x| x * 2).interrupt(),
+            Some(Interrupt::Deadline)
+        );
+        assert_eq!(stopped.done(), None);
+        assert_eq!(Interrupt::Cancelled.to_string(), "cancelled");
+    }
+}
@@ -0,0 +1,355 @@
+//! Compressed sparse row (CSR) graph representation.
+//!
+//! [`CsrGraph`] stores all edges in three flat arrays, which makes it far
+//! more compact and cache-friendly than the nested-container graphs for
+//! large, static graphs.  Nodes are `usize` indices in `0..num_nodes()`.
+
+use std::iter::Copied;
+use std::iter::Zip;
+use std::ops::Range;
+use std::slice::Iter;
+
+use crate::indexer::NodeIndexer;
+use crate::node_map::VecMap;
+use crate::Graph;
+
+/// A directed graph in compressed sparse row form.
+///
+/// The outgoing edges of node `u` occupy the index range
+/// `offsets[u]..offsets[u + 1]` of the `targets` and `weights` arrays.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use digraphx_rs::{CsrGraph, Graph, NegCycleFinder};
+///
+/// let g = CsrGraph::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+/// assert_eq!(g.num_nodes(), 3);
+/// assert_eq!(g.num_edges(), 3);
+///
+/// let mut ncf = NegCycleFinder::new(&g);
+/// let mut dist: HashMap<usize, i32> = HashMap::new();
+/// assert!(ncf.howard(&mut dist, |w| *w).is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrGraph<W> {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<W: Copy> CsrGraph<W> {
+    /// Build a graph from (node, neighbor, weight) triples.
+    ///
+    /// The number of nodes is one more than the largest index mentioned.
+    /// The outgoing edges of each node keep their relative input order.
+    ///
+    /// ```rust
+    /// use digraphx_rs::{CsrGraph, Graph};
+    ///
+    /// let g = CsrGraph::from_edges(&[(0, 2, 5), (0, 1, 4), (3, 0, 1)]);
+    /// assert_eq!(g.num_nodes(), 4);
+    /// assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![(2, 5), (1, 4)]);
+    /// ```
+    pub fn from_edges(edges: &[(usize, usize, W)]) -> Self {
+        let num_nodes = edges
+            .iter()
+            .map(|&(u, v, _)| u.max(v) + 1)
+            .max()
+            .unwrap_or(0);
+        Self::from_edges_with_nodes(num_nodes, edges)
+    }
+
+    /// Build a graph with exactly `num_nodes` nodes from (node, neighbor,
+    /// weight) triples.  Nodes without edges are kept as isolated nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge mentions a node `>= num_nodes`.
+    pub fn from_edges_with_nodes(num_nodes: usize, edges: &[(usize, usize, W)]) -> Self {
+        let mut offsets = vec![0; num_nodes + 1];
+        for &(u, v, _) in edges {
+            assert!(u < num_nodes && v < num_nodes, "edge node out of range");
+            offsets[u + 1] += 1;
+        }
+        for i in 0..num_nodes {
+            offsets[i + 1] += offsets[i];
+        }
+        // Counting sort by source node, stable within each source.
+        let mut next = offsets.clone();
+        let mut slots: Vec<Option<(usize, W)>> = vec![None; edges.len()];
+        for &(u, v, w) in edges {
+            slots[next[u]] = Some((v, w));
+            next[u] += 1;
+        }
+        let (targets, weights) = slots.into_iter().flatten().unzip();
+        CsrGraph {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    /// Build a graph from an adjacency list, keeping node indices and the
+    /// order of each neighbor list.
+    ///
+    /// ```rust
+    /// use digraphx_rs::{CsrGraph, Graph};
+    ///
+    /// let adj = vec![vec![(1, 1.0)], vec![(0, 2.0), (2, 3.0)], vec![]];
+    /// let g = CsrGraph::from_adjacency(&adj);
+    /// assert_eq!(g.num_nodes(), 3);
+    /// assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![(0, 2.0), (2, 3.0)]);
+    /// ```
+    pub fn from_adjacency(adj: &[Vec<(usize, W)>]) -> Self {
+        let mut offsets = Vec::with_capacity(adj.len() + 1);
+        let num_edges = adj.iter().map(Vec::len).sum();
+        let mut targets = Vec::with_capacity(num_edges);
+        let mut weights = Vec::with_capacity(num_edges);
+        offsets.push(0);
+        for nbrs in adj {
+            for &(v, w) in nbrs {
+                targets.push(v);
+                weights.push(w);
+            }
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    /// Build a graph from any [`Graph`], such as a
+    /// `HashMap<N, HashMap<N, W>>`, relabelling its nodes to `0..n`.
+    ///
+    /// Returns the graph together with the relabelling: entry `i` of the
+    /// returned vector is the original node that became node `i`.  Nodes
+    /// that only appear as neighbors are included.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use digraphx_rs::{CsrGraph, Graph};
+    ///
+    /// let g: HashMap<&str, HashMap<&str, i32>> =
+    ///     [("a", [("b", 1)].into()), ("b", [("c", 2)].into())].into();
+    /// let (csr, labels) = CsrGraph::from_graph(&g);
+    /// assert_eq!(csr.num_nodes(), 3);
+    /// let a = labels.iter().position(|&n| n == "a").unwrap();
+    /// let (b, w) = csr.neighbors(a).next().unwrap();
+    /// assert_eq!((labels[b], w), ("b", 1));
+    /// ```
+    pub fn from_graph<G>(graph: &G) -> (Self, Vec<G::Node>)
+    where
+        G: Graph<Edge = W>,
+    {
+        let indexer = NodeIndexer::from_graph(graph);
+        (indexer.csr(graph), indexer.into_labels())
+    }
+}
+
+impl<W> CsrGraph<W> {
+    /// Return the number of edges.
+    #[inline]
+    pub fn num_edges(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Return the number of outgoing edges of `node` (zero if out of range).
+    #[inline]
+    pub fn out_degree(&self, node: usize) -> usize {
+        let range = self.edge_range(node);
+        range.end - range.start
+    }
+
+    /// Return the offset array (length `num_nodes() + 1`).
+    #[inline]
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Return the edge target array.
+    #[inline]
+    pub fn targets(&self) -> &[usize] {
+        &self.targets
+    }
+
+    /// Return the edge weight array, parallel to [`targets`](Self::targets).
+    #[inline]
+    pub fn weights(&self) -> &[W] {
+        &self.weights
+    }
+
+    /// Index range of the outgoing edges of `node` in the edge arrays.
+    #[inline]
+    fn edge_range(&self, node: usize) -> Range<usize> {
+        if node + 1 < self.offsets.len() {
+            self.offsets[node]..self.offsets[node + 1]
+        } else {
+            0..0
+        }
+    }
+}
+
+impl<W: Copy> From<Vec<Vec<(usize, W)>>> for CsrGraph<W> {
+    fn from(adj: Vec<Vec<(usize, W)>>) -> Self {
+        Self::from_adjacency(&adj)
+    }
+}
+
+impl<W: Copy> Graph for CsrGraph<W> {
+    type Node = usize;
+    type Edge = W;
+    type Nodes<'a>
+        = Range<usize>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = Zip<Copied<Iter<'a, usize>>, Copied<Iter<'a, W>>>
+    where
+        Self: 'a;
+
+    type Map<V> = VecMap<V>;
+
+    fn nodes(&self) -> Self::Nodes<'_> {
+        0..self.num_nodes()
+    }
+
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        let range = self.edge_range(node);
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.weights[range].iter().copied())
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    fn node_map<V>(&self) -> Self::Map<V> {
+        VecMap::with_capacity(self.num_nodes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_from_edges;
+    use crate::parametric::{MaxParametricSolver, ParametricAPI};
+    use crate::{bellman_ford, NegCycleFinder, NegCycleFinderQ};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_edges() {
+        let g = CsrGraph::from_edges(&[(1, 0, 2), (0, 1, 1), (1, 2, 3)]);
+        assert_eq!(g.num_nodes(), 3);
+        assert_eq!(g.num_edges(), 3);
+        assert_eq!(g.offsets(), &[0, 1, 3, 3]);
+        assert_eq!(g.targets(), &[1, 0, 2]);
+        assert_eq!(g.weights(), &[1, 2, 3]);
+        assert_eq!(g.out_degree(1), 2);
+        assert_eq!(g.out_degree(2), 0);
+        assert_eq!(g.out_degree(9), 0);
+    }
+
+    #[test]
+    fn test_from_edges_with_isolated_nodes() {
+        let g = CsrGraph::from_edges_with_nodes(5, &[(0, 1, 1.0)]);
+        assert_eq!(g.num_nodes(), 5);
+        assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert!(g.neighbors(4).next().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "edge node out of range")]
+    fn test_from_edges_with_nodes_out_of_range() {
+        CsrGraph::from_edges_with_nodes(2, &[(0, 2, 1.0)]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let g: CsrGraph<i32> = CsrGraph::from_edges(&[]);
+        assert_eq!(g.num_nodes(), 0);
+        assert_eq!(g.num_edges(), 0);
+        assert!(g.neighbors(0).next().is_none());
+    }
+
+    #[test]
+    fn test_from_adjacency_matches_vec_graph() {
+        let adj: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1), (2, 2)], vec![(2, 3)], vec![(0, -4)]];
+        let g = CsrGraph::from(adj.clone());
+        assert_eq!(g.num_nodes(), adj.num_nodes());
+        for u in adj.nodes() {
+            assert_eq!(
+                g.neighbors(u).collect::<Vec<_>>(),
+                adj.neighbors(u).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_graph_relabels() {
+        let g = graph_from_edges(&[("a", "b", 1), ("b", "c", 2), ("c", "a", -4)]);
+        let (csr, labels) = CsrGraph::from_graph(&g);
+        assert_eq!(csr.num_nodes(), 3);
+        assert_eq!(csr.num_edges(), 3);
+        for u in csr.nodes() {
+            for (v, w) in csr.neighbors(u) {
+                assert_eq!(g[&labels[u]][&labels[v]], w);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_graph_neighbor_only_nodes() {
+        // "c" has no entry of its own in the outer map
+        let g: HashMap<&str, HashMap<&str, i32>> =
+            [("a", [("b", 1)].into()), ("b", [("c", 1)].into())].into();
+        let (csr, labels) = CsrGraph::from_graph(&g);
+        assert_eq!(csr.num_nodes(), 3);
+        assert!(labels.contains(&"c"));
+    }
+
+    #[test]
+    fn test_neg_cycle() {
+        let g = CsrGraph::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+        let mut ncf = NegCycleFinder::new(&g);
+        let mut dist: HashMap<usize, i32> = HashMap::new();
+        let cycle = ncf.howard(&mut dist, |w| *w).unwrap();
+        assert_eq!(cycle.weight, -1);
+
+        let mut ncfq = NegCycleFinderQ::new(&g);
+        let mut dist: HashMap<usize, i32> = HashMap::new();
+        assert!(ncfq.howard_succ(&mut dist, |w| *w, |_, _| true).is_some());
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let g = CsrGraph::from_edges(&[(0, 1, 4), (1, 2, 3), (0, 2, 10)]);
+        let paths = bellman_ford(&g, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(2), Some(7));
+    }
+
+    #[test]
+    fn test_parametric() {
+        struct MinMean;
+
+        impl ParametricAPI<f64> for MinMean {
+            fn distance(&self, r: &f64, w: &f64) -> f64 {
+                *w - *r
+            }
+            fn zero_cancel(&self, cycle: &[f64]) -> f64 {
+                cycle.iter().sum::<f64>() / cycle.len() as f64
+            }
+        }
+
+        let g = CsrGraph::from_edges(&[(0, 1, 5.0), (1, 0, 1.0), (1, 2, 1.0), (2, 1, 2.0)]);
+        let mut solver = MaxParametricSolver::new(&g, MinMean);
+        let dist: HashMap<usize, f64> = HashMap::new();
+        let result = solver.run(dist, 100.0);
+        assert_eq!(result.ratio, 1.5);
+    }
+}
@@ -0,0 +1,219 @@
+//! Cycle result type shared by the negative-cycle and parametric solvers.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Add;
+
+use num::CheckedAdd;
+
+use crate::error::Error;
+use crate::node_map::NodeMap;
+use crate::Zero;
+
+/// A directed cycle reported by a solver.
+///
+/// The cycle is stored in traversal order: `edges[i]` goes from `nodes[i]` to
+/// `nodes[(i + 1) % len]`, so the last arc closes the cycle back to
+/// `nodes[0]`.  `weight` is the total of the arc weights in the distance
+/// domain `D` the solver was run in.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use digraphx_rs::{graph_from_edges, NegCycleFinder};
+///
+/// let graph = graph_from_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+/// let mut ncf = NegCycleFinder::new(&graph);
+/// let mut dist: HashMap<i32, i32> = HashMap::new();
+/// let cycle = ncf.howard(&mut dist, |w| *w).unwrap();
+///
+/// assert_eq!(cycle.len(), 3);
+/// assert_eq!(cycle.weight, -1);
+/// for (i, &(u, v, _)) in cycle.edges.iter().enumerate() {
+///     assert_eq!(u, cycle.nodes[i]);
+///     assert_eq!(v, cycle.nodes[(i + 1) % cycle.len()]);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle<N, E, D> {
+    /// Nodes of the cycle in traversal order.
+    pub nodes: Vec<N>,
+    /// Arcs of the cycle as `(u, v, edge)` triples in traversal order.
+    pub edges: Vec<(N, N, E)>,
+    /// Total weight of the cycle in the distance domain.
+    pub weight: D,
+}
+
+impl<N, E, D> Cycle<N, E, D> {
+    /// Return the number of arcs (equivalently, nodes) on the cycle.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Return `true` when the cycle has no arcs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Iterate over the edge payloads of the cycle in traversal order.
+    #[inline]
+    pub fn payloads(&self) -> impl Iterator<Item = &E> {
+        self.edges.iter().map(|(_, _, e)| e)
+    }
+}
+
+impl<N, E, D> Cycle<N, E, D>
+where
+    N: Copy + Eq + Hash,
+    E: Copy,
+{
+    /// Collect the arcs of the cycle through `handle` from a predecessor
+    /// map, in traversal order.  Fails with the first node on the walk that
+    /// has no predecessor, or with `handle` if the walk runs into a cycle
+    /// that does not pass through it.
+    fn arcs_from_pred<P>(handle: N, pred: &P) -> Result<Vec<(N, N, E)>, N>
+    where
+        P: NodeMap<N, (N, E)>,
+    {
+        let mut vtx = handle;
+        let mut edges = Vec::new();
+        let mut seen = HashSet::new();
+        loop {
+            if !seen.insert(vtx) {
+                return Err(handle);
+            }
+            let &(u, e) = pred.get(vtx).ok_or(vtx)?;
+            edges.push((u, vtx, e));
+            vtx = u;
+            if vtx == handle {
+                break;
+            }
+        }
+        edges.reverse();
+        Ok(edges)
+    }
+
+    fn from_arcs(edges: Vec<(N, N, E)>, weight: D) -> Self {
+        let nodes = edges.iter().map(|&(u, _, _)| u).collect();
+        Cycle {
+            nodes,
+            edges,
+            weight,
+        }
+    }
+
+    /// Reconstruct the cycle through `handle` from a predecessor map.
+    ///
+    /// `pred` maps each node to the tail and payload of its incoming arc.
+    /// Fails with [`Error::InconsistentGraph`] if the walk from `handle`
+    /// breaks off before returning to it.
+    pub(crate) fn try_from_pred<P, F>(handle: N, pred: &P, get_weight: &F) -> Result<Self, Error<N>>
+    where
+        D: Add<Output = D> + Copy + Zero,
+        P: NodeMap<N, (N, E)>,
+        F: Fn(&E) -> D,
+    {
+        let edges = Self::arcs_from_pred(handle, pred).map_err(Error::InconsistentGraph)?;
+        let weight = edges
+            .iter()
+            .fold(D::zero(), |acc, (_, _, e)| acc + get_weight(e));
+        Ok(Self::from_arcs(edges, weight))
+    }
+
+    /// Like [`try_from_pred`](Self::try_from_pred), but also fails with
+    /// [`Error::Overflow`] at the arc where the total weight leaves the
+    /// range of `D`.
+    pub(crate) fn from_pred_checked<P, F>(
+        handle: N,
+        pred: &P,
+        get_weight: &F,
+    ) -> Result<Self, Error<N>>
+    where
+        D: CheckedAdd + Copy + Zero,
+        P: NodeMap<N, (N, E)>,
+        F: Fn(&E) -> D,
+    {
+        let edges = Self::arcs_from_pred(handle, pred).map_err(Error::InconsistentGraph)?;
+        let weight = edges.iter().try_fold(D::zero(), |acc, &(u, v, e)| {
+            acc.checked_add(&get_weight(&e))
+                .ok_or(Error::Overflow { tail: u, head: v })
+        })?;
+        Ok(Self::from_arcs(edges, weight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn triangle() -> Cycle<char, i32, i32> {
+        Cycle {
+            nodes: vec!['a', 'b', 'c'],
+            edges: vec![('a', 'b', 1), ('b', 'c', 1), ('c', 'a', -3)],
+            weight: -1,
+        }
+    }
+
+    #[test]
+    fn test_len() {
+        let c = triangle();
+        assert_eq!(c.len(), 3);
+        assert!(!c.is_empty());
+    }
+
+    #[test]
+    fn test_payloads() {
+        let c = triangle();
+        assert_eq!(c.payloads().copied().collect::<Vec<_>>(), vec![1, 1, -3]);
+    }
+
+    #[test]
+    fn test_from_pred() {
+        let pred: HashMap<char, (char, i32)> = [
+            ('b', ('a', 1)),
+            ('c', ('b', 1)),
+            ('a', ('c', -3)),
+            ('d', ('a', 7)),
+        ]
+        .into();
+        let c = Cycle::try_from_pred('a', &pred, &|w: &i32| *w).unwrap();
+        assert_eq!(c, triangle());
+
+        // 'd' hangs off the cycle, and 'a' has no predecessor once removed
+        let res = Cycle::try_from_pred('d', &pred, &|w: &i32| *w);
+        assert_eq!(res, Err(Error::InconsistentGraph('d')));
+        let mut broken = pred.clone();
+        broken.remove(&'a');
+        let res = Cycle::try_from_pred('b', &broken, &|w: &i32| *w);
+        assert_eq!(res, Err(Error::InconsistentGraph('a')));
+        let res = Cycle::from_pred_checked('b', &broken, &|w: &i32| *w);
+        assert_eq!(res, Err(Error::InconsistentGraph('a')));
+    }
+
+    #[test]
+    fn test_from_pred_checked_overflow() {
+        let pred: HashMap<char, (char, i32)> = [('b', ('a', i32::MIN)), ('a', ('b', -1))].into();
+        let res = Cycle::from_pred_checked('a', &pred, &|w: &i32| *w);
+        assert_eq!(
+            res,
+            Err(Error::Overflow {
+                tail: 'b',
+                head: 'a'
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let c: Cycle<char, i32, i32> = Cycle {
+            nodes: vec![],
+            edges: vec![],
+            weight: 0,
+        };
+        assert!(c.is_empty());
+    }
+}
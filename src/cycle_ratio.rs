@@ -0,0 +1,583 @@
+//! Minimum and maximum cycle ratio by Howard's policy iteration.
+//!
+//! Every edge supplies a `(cost, time)` pair and the ratio of a cycle $C$ is
+//!
+//! $$ \lambda(C) = \frac{\sum_{e \in C} \text{cost}(e)}{\sum_{e \in C} \text{time}(e)} $$
+//!
+//! [`min_cycle_ratio`] runs Howard's policy iteration directly on the
+//! pairs: each node keeps one outgoing arc (the *policy*), the cycles of the
+//! policy graph give every node a ratio $\eta$ and a potential $x$, and
+//! nodes switch to arcs that lower $\eta$ or, at equal $\eta$, lower $x$
+//! until no arc improves.  This avoids the single-weight restriction of
+//! [`ParametricAPI::zero_cancel`](crate::ParametricAPI::zero_cancel), and
+//! with `num::rational::Ratio` the result is exact.
+//!
+//! Times must be non-negative.  A cycle with zero total time has no ratio,
+//! so it is rejected with a [`ZeroTimeCycle`] error.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::indexer::NodeIndexer;
+use crate::{Cycle, Graph, Zero};
+
+/// A critical cycle of a cycle ratio problem.
+///
+/// `cycle.weight` is the total cost of the cycle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatioCycle<N, E, D> {
+    /// Ratio of the cycle, `cycle.weight / time`.
+    pub ratio: D,
+    /// Total time of the cycle.
+    pub time: D,
+    /// The cycle; its `weight` is the total cost.
+    pub cycle: Cycle<N, E, D>,
+}
+
+/// Error returned when the graph has a cycle with zero total time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZeroTimeCycle<N, E, D> {
+    /// A cycle with zero total time; its `weight` is the total cost.
+    pub cycle: Cycle<N, E, D>,
+}
+
+impl<N, E, D> fmt::Display for ZeroTimeCycle<N, E, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cycle of length {} has zero total time",
+            self.cycle.len()
+        )
+    }
+}
+
+impl<N: fmt::Debug, E: fmt::Debug, D: fmt::Debug> std::error::Error for ZeroTimeCycle<N, E, D> {}
+
+/// Result of [`min_cycle_ratio`] and [`max_cycle_ratio`]: `Ok(None)` if the
+/// graph is acyclic.
+pub type CycleRatioResult<N, E, D> = Result<Option<RatioCycle<N, E, D>>, ZeroTimeCycle<N, E, D>>;
+
+/// Find a cycle minimizing total cost over total time.
+///
+/// `get_cost_time` maps each edge payload to its `(cost, time)` pair.
+/// Returns `Ok(None)` if the graph has no cycle, or a [`ZeroTimeCycle`]
+/// error if some cycle has zero total time.
+///
+/// # Example
+///
+/// ```rust
+/// use num::rational::Ratio;
+/// use digraphx_rs::cycle_ratio::min_cycle_ratio;
+/// use digraphx_rs::graph_from_edges;
+///
+/// // Edge payloads are (cost, time)
+/// let graph = graph_from_edges(&[(0, 1, (5, 1)), (1, 0, (1, 1)), (1, 2, (1, 2)), (2, 1, (2, 2))]);
+/// let rc = min_cycle_ratio(&graph, |&(c, t)| (Ratio::from_integer(c), Ratio::from_integer(t)))
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(rc.ratio, Ratio::new(3, 4));
+/// assert_eq!(rc.cycle.len(), 2);
+/// ```
+pub fn min_cycle_ratio<G, D, F>(
+    graph: &G,
+    get_cost_time: F,
+) -> CycleRatioResult<G::Node, G::Edge, D>
+where
+    G: Graph,
+    D: Add<Output = D>
+        + Sub<Output = D>
+        + Mul<Output = D>
+        + Div<Output = D>
+        + PartialOrd
+        + Copy
+        + Zero,
+    F: Fn(&G::Edge) -> (D, D),
+{
+    howard_ratio(graph, get_cost_time)
+}
+
+/// Find a cycle maximizing total cost over total time.
+///
+/// Solved as the minimum cycle ratio with negated costs; see
+/// [`min_cycle_ratio`].
+///
+/// ```rust
+/// use digraphx_rs::cycle_ratio::max_cycle_ratio;
+/// use digraphx_rs::graph_from_edges;
+///
+/// let graph = graph_from_edges(&[(0, 1, (5.0, 1.0)), (1, 0, (1.0, 1.0)), (1, 1, (2.5, 1.0))]);
+/// let rc = max_cycle_ratio(&graph, |&p| p).unwrap().unwrap();
+/// assert_eq!(rc.ratio, 3.0);
+/// assert_eq!(rc.cycle.weight, 6.0);
+/// ```
+pub fn max_cycle_ratio<G, D, F>(
+    graph: &G,
+    get_cost_time: F,
+) -> CycleRatioResult<G::Node, G::Edge, D>
+where
+    G: Graph,
+    D: Add<Output = D>
+        + Sub<Output = D>
+        + Mul<Output = D>
+        + Div<Output = D>
+        + PartialOrd
+        + Copy
+        + Zero,
+    F: Fn(&G::Edge) -> (D, D),
+{
+    let negate = |x: D| D::zero() - x;
+    let negated = howard_ratio(graph, |e| {
+        let (c, t) = get_cost_time(e);
+        (negate(c), t)
+    });
+    let flip = |mut cycle: Cycle<G::Node, G::Edge, D>| {
+        cycle.weight = negate(cycle.weight);
+        cycle
+    };
+    match negated {
+        Ok(found) => Ok(found.map(|rc| RatioCycle {
+            ratio: negate(rc.ratio),
+            time: rc.time,
+            cycle: flip(rc.cycle),
+        })),
+        Err(err) => Err(ZeroTimeCycle {
+            cycle: flip(err.cycle),
+        }),
+    }
+}
+
+fn howard_ratio<G, D, F>(graph: &G, get_cost_time: F) -> CycleRatioResult<G::Node, G::Edge, D>
+where
+    G: Graph,
+    D: Add<Output = D>
+        + Sub<Output = D>
+        + Mul<Output = D>
+        + Div<Output = D>
+        + PartialOrd
+        + Copy
+        + Zero,
+    F: Fn(&G::Edge) -> (D, D),
+{
+    let indexer = NodeIndexer::from_graph(graph);
+    let adj = indexer.adjacency(graph);
+    let n = adj.len();
+    let to_cycle = |arcs: &[(usize, usize, G::Edge)]| {
+        let labels = indexer.labels();
+        let edges: Vec<_> = arcs
+            .iter()
+            .map(|&(u, v, e)| (labels[u], labels[v], e))
+            .collect();
+        let weight = arcs
+            .iter()
+            .fold(D::zero(), |acc, (_, _, e)| acc + get_cost_time(e).0);
+        Cycle {
+            nodes: edges.iter().map(|&(u, _, _)| u).collect(),
+            edges,
+            weight,
+        }
+    };
+
+    if let Some(arcs) = zero_time_cycle(&adj, &get_cost_time) {
+        return Err(ZeroTimeCycle {
+            cycle: to_cycle(&arcs),
+        });
+    }
+
+    // Only nodes that can keep walking forever take part; prune the rest.
+    let alive = prune_sinks(&adj);
+
+    // Initial policy: the cheapest arc into the surviving subgraph.
+    let mut policy: Vec<Option<(usize, G::Edge)>> = vec![None; n];
+    for u in (0..n).filter(|&u| alive[u]) {
+        for &(v, e) in adj[u].iter().filter(|&&(v, _)| alive[v]) {
+            let better = match policy[u] {
+                None => true,
+                Some((_, best)) => get_cost_time(&e).0 < get_cost_time(&best).0,
+            };
+            if better {
+                policy[u] = Some((v, e));
+            }
+        }
+    }
+    if policy.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+
+    let mut eta = vec![D::zero(); n];
+    let mut x = vec![D::zero(); n];
+    loop {
+        // Value determination on the policy graph.
+        let handles = match evaluate(&policy, &get_cost_time, &mut eta, &mut x) {
+            Ok(handles) => handles,
+            Err(arcs) => {
+                return Err(ZeroTimeCycle {
+                    cycle: to_cycle(&arcs),
+                })
+            }
+        };
+
+        // Policy improvement: prefer a lower ratio, then a lower potential.
+        let mut changed = false;
+        for u in (0..n).filter(|&u| alive[u]) {
+            let mut best: Option<(usize, G::Edge)> = None;
+            let mut best_eta = eta[u];
+            for &(v, e) in adj[u].iter().filter(|&&(v, _)| alive[v]) {
+                if eta[v] < best_eta {
+                    best_eta = eta[v];
+                    best = Some((v, e));
+                }
+            }
+            if best.is_none() {
+                let mut best_x = x[u];
+                for &(v, e) in adj[u].iter().filter(|&&(v, _)| alive[v]) {
+                    if eta[v] == eta[u] {
+                        let (c, t) = get_cost_time(&e);
+                        let xv = c - eta[u] * t + x[v];
+                        if xv < best_x {
+                            best_x = xv;
+                            best = Some((v, e));
+                        }
+                    }
+                }
+            }
+            if best.is_some() {
+                policy[u] = best;
+                changed = true;
+            }
+        }
+        if changed {
+            continue;
+        }
+
+        let &h = handles
+            .iter()
+            .min_by(|&&a, &&b| eta[a].partial_cmp(&eta[b]).unwrap())
+            .unwrap();
+        let arcs = policy_cycle(&policy, h);
+        let cycle = to_cycle(&arcs);
+        let time = arcs
+            .iter()
+            .fold(D::zero(), |acc, (_, _, e)| acc + get_cost_time(e).1);
+        return Ok(Some(RatioCycle {
+            ratio: eta[h],
+            time,
+            cycle,
+        }));
+    }
+}
+
+/// Mark the nodes from which an infinite walk exists.
+fn prune_sinks<E>(adj: &[Vec<(usize, E)>]) -> Vec<bool> {
+    let n = adj.len();
+    let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut out_degree: Vec<usize> = vec![0; n];
+    for (u, nbrs) in adj.iter().enumerate() {
+        out_degree[u] = nbrs.len();
+        for &(v, _) in nbrs {
+            preds[v].push(u);
+        }
+    }
+    let mut alive = vec![true; n];
+    let mut stack: Vec<usize> = (0..n).filter(|&u| out_degree[u] == 0).collect();
+    while let Some(v) = stack.pop() {
+        alive[v] = false;
+        for &u in &preds[v] {
+            out_degree[u] -= 1;
+            if out_degree[u] == 0 {
+                stack.push(u);
+            }
+        }
+    }
+    alive
+}
+
+/// Compute the ratio `eta` and potential `x` of every node under `policy`.
+///
+/// Returns one handle node per policy cycle, or the arcs of a policy cycle
+/// with zero total time.
+#[allow(clippy::type_complexity)]
+fn evaluate<E, D, F>(
+    policy: &[Option<(usize, E)>],
+    get_cost_time: &F,
+    eta: &mut [D],
+    x: &mut [D],
+) -> Result<Vec<usize>, Vec<(usize, usize, E)>>
+where
+    E: Copy,
+    D: Add<Output = D>
+        + Sub<Output = D>
+        + Mul<Output = D>
+        + Div<Output = D>
+        + PartialOrd
+        + Copy
+        + Zero,
+    F: Fn(&E) -> (D, D),
+{
+    const NEW: u8 = 0;
+    const ACTIVE: u8 = 1;
+    const DONE: u8 = 2;
+
+    let n = policy.len();
+    let mut state = vec![NEW; n];
+    let mut handles = Vec::new();
+    let mut stack = Vec::new();
+    for start in 0..n {
+        if policy[start].is_none() || state[start] != NEW {
+            continue;
+        }
+        let mut u = start;
+        while state[u] == NEW {
+            state[u] = ACTIVE;
+            stack.push(u);
+            u = policy[u].unwrap().0;
+        }
+        if state[u] == ACTIVE {
+            // `u` closes a new cycle
+            let arcs = policy_cycle(policy, u);
+            let (cost, time) = arcs
+                .iter()
+                .fold((D::zero(), D::zero()), |(c, t), (_, _, e)| {
+                    let (ce, te) = get_cost_time(e);
+                    (c + ce, t + te)
+                });
+            if time == D::zero() {
+                return Err(arcs);
+            }
+            eta[u] = cost / time;
+            x[u] = D::zero();
+            state[u] = DONE;
+            handles.push(u);
+        }
+        while let Some(v) = stack.pop() {
+            if state[v] == DONE {
+                continue;
+            }
+            let (w, e) = policy[v].unwrap();
+            let (c, t) = get_cost_time(&e);
+            eta[v] = eta[w];
+            x[v] = c - eta[w] * t + x[w];
+            state[v] = DONE;
+        }
+    }
+    Ok(handles)
+}
+
+/// Return the arcs of the policy cycle through `handle` in traversal order.
+fn policy_cycle<E: Copy>(policy: &[Option<(usize, E)>], handle: usize) -> Vec<(usize, usize, E)> {
+    let mut arcs = Vec::new();
+    let mut u = handle;
+    loop {
+        let (v, e) = policy[u].unwrap();
+        arcs.push((u, v, e));
+        u = v;
+        if u == handle {
+            return arcs;
+        }
+    }
+}
+
+/// Find a cycle made of zero-time arcs, if any.
+fn zero_time_cycle<E, D, F>(
+    adj: &[Vec<(usize, E)>],
+    get_cost_time: &F,
+) -> Option<Vec<(usize, usize, E)>>
+where
+    E: Copy,
+    D: PartialOrd + Zero,
+    F: Fn(&E) -> (D, D),
+{
+    let n = adj.len();
+    let zero: Vec<Vec<(usize, E)>> = adj
+        .iter()
+        .map(|nbrs| {
+            nbrs.iter()
+                .copied()
+                .filter(|(_, e)| get_cost_time(e).1 == D::zero())
+                .collect()
+        })
+        .collect();
+
+    // Iterative depth-first search; `on_path[v]` holds the arc into `v`.
+    let mut done = vec![false; n];
+    let mut on_path: Vec<Option<Option<(usize, E)>>> = vec![None; n];
+    for root in 0..n {
+        if done[root] {
+            continue;
+        }
+        let mut stack = vec![(root, 0)];
+        on_path[root] = Some(None);
+        while let Some(&mut (u, ref mut i)) = stack.last_mut() {
+            if let Some(&(v, e)) = zero[u].get(*i) {
+                *i += 1;
+                if on_path[v].is_some() {
+                    // Back arc: unwind the path from `u` to `v`.
+                    let mut arcs = vec![(u, v, e)];
+                    let mut w = u;
+                    while w != v {
+                        let (p, pe) = on_path[w].unwrap().unwrap();
+                        arcs.push((p, w, pe));
+                        w = p;
+                    }
+                    arcs.reverse();
+                    return Some(arcs);
+                }
+                if !done[v] {
+                    on_path[v] = Some(Some((u, e)));
+                    stack.push((v, 0));
+                }
+            } else {
+                on_path[u] = None;
+                done[u] = true;
+                stack.pop();
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parametric::{MaxParametricSolver, ParametricAPI};
+    use crate::testing::{random_edges, Lcg};
+    use crate::{graph_from_edges, min_mean_cycle};
+    use num::rational::Ratio;
+    use std::collections::HashMap;
+
+    type Q = Ratio<i32>;
+
+    fn q(&(c, t): &(i32, i32)) -> (Q, Q) {
+        (Q::from_integer(c), Q::from_integer(t))
+    }
+
+    fn check(rc: &RatioCycle<u32, (i32, i32), Q>, graph: &HashMap<u32, HashMap<u32, (i32, i32)>>) {
+        let c = &rc.cycle;
+        let mut cost = 0;
+        let mut time = 0;
+        for (i, &(u, v, e)) in c.edges.iter().enumerate() {
+            assert_eq!(u, c.nodes[i]);
+            assert_eq!(v, c.nodes[(i + 1) % c.len()]);
+            assert_eq!(graph[&u][&v], e);
+            cost += e.0;
+            time += e.1;
+        }
+        assert_eq!(c.weight, Q::from_integer(cost));
+        assert_eq!(rc.time, Q::from_integer(time));
+        assert_eq!(rc.ratio, Q::new(cost, time));
+    }
+
+    #[test]
+    fn test_min_ratio() {
+        // Ratios: 0 <-> 1 is 6/2, 1 <-> 2 is 3/4, 2 -> 3 -> 2 is 4/3
+        let graph = graph_from_edges(&[
+            (0u32, 1, (5, 1)),
+            (1, 0, (1, 1)),
+            (1, 2, (1, 2)),
+            (2, 1, (2, 2)),
+            (2, 3, (1, 1)),
+            (3, 2, (3, 2)),
+        ]);
+        let rc = min_cycle_ratio(&graph, q).unwrap().unwrap();
+        assert_eq!(rc.ratio, Q::new(3, 4));
+        check(&rc, &graph);
+    }
+
+    #[test]
+    fn test_max_ratio() {
+        let graph = graph_from_edges(&[
+            (0u32, 1, (5, 1)),
+            (1, 0, (1, 1)),
+            (1, 2, (1, 2)),
+            (2, 1, (2, 2)),
+        ]);
+        let rc = max_cycle_ratio(&graph, q).unwrap().unwrap();
+        assert_eq!(rc.ratio, Q::from_integer(3));
+        check(&rc, &graph);
+    }
+
+    #[test]
+    fn test_acyclic() {
+        let graph = graph_from_edges(&[(0u32, 1, (1, 1)), (1, 2, (1, 0))]);
+        assert_eq!(min_cycle_ratio(&graph, q), Ok(None));
+        assert_eq!(max_cycle_ratio(&graph, q), Ok(None));
+    }
+
+    #[test]
+    fn test_tail_into_cycle() {
+        // 0 -> 1 only leads into the self-loop at 1; 2 is a sink
+        let graph = graph_from_edges(&[(0u32, 1, (-9, 1)), (1, 1, (2, 3)), (1, 2, (-5, 1))]);
+        let rc = min_cycle_ratio(&graph, q).unwrap().unwrap();
+        assert_eq!(rc.ratio, Q::new(2, 3));
+        assert_eq!(rc.cycle.nodes, vec![1]);
+    }
+
+    #[test]
+    fn test_zero_time_cycle() {
+        let graph = graph_from_edges(&[
+            (0u32, 1, (1, 1)),
+            (1, 2, (4, 0)),
+            (2, 3, (-1, 0)),
+            (3, 1, (2, 0)),
+        ]);
+        let err = min_cycle_ratio(&graph, q).unwrap_err();
+        assert_eq!(err.cycle.len(), 3);
+        assert_eq!(err.cycle.weight, Q::from_integer(5));
+        assert!(err.to_string().contains("zero total time"));
+        let err = max_cycle_ratio(&graph, q).unwrap_err();
+        assert_eq!(err.cycle.weight, Q::from_integer(5));
+    }
+
+    #[test]
+    fn test_string_nodes_f64() {
+        let graph = graph_from_edges(&[("a", "b", (2.0, 1.0)), ("b", "a", (1.0, 2.0))]);
+        let rc = min_cycle_ratio(&graph, |&p| p).unwrap().unwrap();
+        assert_eq!(rc.ratio, 1.0);
+        assert_eq!(rc.cycle.len(), 2);
+    }
+
+    struct CostTime;
+
+    impl ParametricAPI<(i32, i32), Q> for CostTime {
+        fn distance(&self, r: &Q, &(c, t): &(i32, i32)) -> Q {
+            Q::from_integer(c) - *r * t
+        }
+        fn zero_cancel(&self, cycle: &[(i32, i32)]) -> Q {
+            let c: i32 = cycle.iter().map(|e| e.0).sum();
+            let t: i32 = cycle.iter().map(|e| e.1).sum();
+            Q::new(c, t)
+        }
+    }
+
+    #[test]
+    fn test_matches_parametric_and_karp() {
+        let mut rng = Lcg(7);
+        for _ in 0..50 {
+            let edges = random_edges(&mut rng, 8, |rng| {
+                (rng.next(21) as i32 - 10, 1 + rng.next(4) as i32)
+            });
+            let graph = graph_from_edges(&edges);
+
+            let howard = min_cycle_ratio(&graph, q).unwrap();
+            let mut solver = MaxParametricSolver::new(&graph, CostTime);
+            let dist: HashMap<u32, Q> = HashMap::new();
+            let result = solver.run(dist, Q::from_integer(100));
+            let r = result.ratio;
+            let param = result.cycle;
+            match &howard {
+                Some(rc) => {
+                    assert!(param.is_some());
+                    assert_eq!(rc.ratio, r);
+                    check(rc, &graph);
+                }
+                None => assert!(param.is_none()),
+            }
+
+            // With unit times the ratio is the cycle mean
+            let unit = min_cycle_ratio(&graph, |&(c, _)| (Q::from_integer(c), Q::from_integer(1)))
+                .unwrap()
+                .map(|rc| rc.ratio);
+            let karp = min_mean_cycle(&graph, |&(c, _)| Q::from_integer(c)).map(|mc| mc.mean);
+            assert_eq!(unit, karp);
+        }
+    }
+}
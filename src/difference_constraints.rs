@@ -0,0 +1,380 @@
+//! Systems of difference constraints solved by negative-cycle detection.
+//!
+//! A system of constraints $x_j - x_i \le c_{ij}$ is feasible exactly when
+//! its constraint graph, with an arc $i \to j$ of weight $c_{ij}$ for every
+//! constraint, has no negative cycle.  In that case the shortest-path
+//! distances form a feasible assignment; otherwise the constraints on a
+//! negative cycle are a certificate of infeasibility.
+//!
+//! [`DifferenceConstraints`] collects typed constraints over arbitrary
+//! variable keys, compacts the variables to dense indices and runs
+//! [`NegCycleFinder::howard`] on the constraint graph.
+//!
+//! # Example
+//!
+//! ```rust
+//! use digraphx_rs::difference_constraints::DifferenceConstraints;
+//!
+//! let mut sys = DifferenceConstraints::new();
+//! sys.fix("start", 0);
+//! sys.less_eq("end", "start", 10); // end - start <= 10
+//! sys.greater_eq("end", "mid", 4); // end - mid >= 4
+//! sys.less("start", "mid", 0); //     start - mid < 0
+//!
+//! let x = sys.solve().unwrap();
+//! assert_eq!(x["start"], 0);
+//! assert!(x["end"] - x["start"] <= 10);
+//! assert!(x["end"] - x["mid"] >= 4);
+//! assert!(x["start"] < x["mid"]);
+//!
+//! sys.less_eq("end", "start", 4); // end - start <= 4, but end >= mid + 4 > start + 4
+//! let err = sys.solve().unwrap_err();
+//! assert_eq!(err.constraints.len(), 3);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use num::Integer;
+
+use crate::indexer::NodeIndexer;
+use crate::NegCycleFinder;
+use crate::Zero;
+
+/// A single constraint of a [`DifferenceConstraints`] system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint<V, D> {
+    /// $x_j - x_i \le \text{bound}$.
+    LessEq {
+        /// The variable being bounded from above.
+        j: V,
+        /// The variable it is measured against.
+        i: V,
+        /// The upper bound on the difference.
+        bound: D,
+    },
+    /// $x_j - x_i < \text{bound}$, for integer domains.
+    Less {
+        /// The variable being bounded from above.
+        j: V,
+        /// The variable it is measured against.
+        i: V,
+        /// The strict upper bound on the difference.
+        bound: D,
+    },
+    /// $x_v = \text{value}$.
+    Fixed {
+        /// The fixed variable.
+        var: V,
+        /// Its value.
+        value: D,
+    },
+}
+
+/// Certificate that a [`DifferenceConstraints`] system is infeasible.
+///
+/// The constraints are listed in the order their arcs appear on the
+/// negative cycle of the constraint graph; summing them yields the
+/// contradiction $0 \le \text{weight} < 0$.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Infeasible<V, D> {
+    /// The conflicting constraints.
+    pub constraints: Vec<Constraint<V, D>>,
+    /// Total weight of the negative cycle they form.
+    pub weight: D,
+}
+
+impl<V, D> fmt::Display for Infeasible<V, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "infeasible difference constraints: {} constraints form a negative cycle",
+            self.constraints.len()
+        )
+    }
+}
+
+impl<V: fmt::Debug, D: fmt::Debug> std::error::Error for Infeasible<V, D> {}
+
+/// Result of [`DifferenceConstraints::solve`].
+pub type SolveResult<V, D> = Result<HashMap<V, D>, Infeasible<V, D>>;
+
+/// Builder and solver for a system of difference constraints.
+///
+/// Variables are any `Copy + Eq + Hash` keys and are registered the first
+/// time they appear.  Constraint bounds and the returned assignment live in
+/// the domain `D`.
+#[derive(Debug, Clone)]
+pub struct DifferenceConstraints<V, D> {
+    vars: NodeIndexer<V>,
+    constraints: Vec<Constraint<V, D>>,
+    // Non-strict bound (or fixed value) of each constraint.
+    bounds: Vec<D>,
+}
+
+impl<V, D> DifferenceConstraints<V, D>
+where
+    V: Copy + Eq + Hash,
+    D: Add<Output = D> + Sub<Output = D> + PartialOrd + Copy + Zero,
+{
+    /// Create an empty system.
+    pub fn new() -> Self {
+        DifferenceConstraints {
+            vars: NodeIndexer::new(),
+            constraints: Vec::new(),
+            bounds: Vec::new(),
+        }
+    }
+
+    /// Register `var` without constraining it.  Unconstrained variables are
+    /// assigned zero.
+    pub fn add_variable(&mut self, var: V) -> &mut Self {
+        self.vars.insert(var);
+        self
+    }
+
+    /// Add the constraint $x_j - x_i \le \text{bound}$.
+    pub fn less_eq(&mut self, j: V, i: V, bound: D) -> &mut Self {
+        self.push(Constraint::LessEq { j, i, bound }, bound)
+    }
+
+    /// Add the constraint $x_j - x_i \ge \text{bound}$, that is
+    /// $x_i - x_j \le -\text{bound}$.
+    pub fn greater_eq(&mut self, j: V, i: V, bound: D) -> &mut Self {
+        self.less_eq(i, j, D::zero() - bound)
+    }
+
+    /// Fix $x_v = \text{value}$.
+    ///
+    /// Fixed values are measured against an implicit reference variable
+    /// pinned at zero, so the assignment returned by
+    /// [`solve`](Self::solve) reproduces them exactly.
+    pub fn fix(&mut self, var: V, value: D) -> &mut Self {
+        self.push(Constraint::Fixed { var, value }, value)
+    }
+
+    /// Return the constraints added so far.
+    #[inline]
+    pub fn constraints(&self) -> &[Constraint<V, D>] {
+        &self.constraints
+    }
+
+    /// Return the number of variables.
+    #[inline]
+    pub fn num_variables(&self) -> usize {
+        self.vars.len()
+    }
+
+    fn push(&mut self, constraint: Constraint<V, D>, bound: D) -> &mut Self {
+        match constraint {
+            Constraint::LessEq { j, i, .. } | Constraint::Less { j, i, .. } => {
+                self.vars.insert(i);
+                self.vars.insert(j);
+            }
+            Constraint::Fixed { var, .. } => {
+                self.vars.insert(var);
+            }
+        }
+        self.constraints.push(constraint);
+        self.bounds.push(bound);
+        self
+    }
+
+    /// Solve the system.
+    ///
+    /// Returns a feasible assignment for every registered variable, or an
+    /// [`Infeasible`] certificate listing the constraints on a negative
+    /// cycle of the constraint graph.  When no variable is fixed the
+    /// assignment is the one with every value at most zero that is
+    /// closest to zero.
+    ///
+    /// Complexity: that of [`NegCycleFinder::howard`] on a graph with one
+    /// node per variable (plus one for the fixed-value reference) and one
+    /// arc per constraint (two per fixed variable).
+    pub fn solve(&self) -> SolveResult<V, D> {
+        let n = self.vars.len();
+        let origin = n;
+        let index = |v: V| self.vars.index(v).unwrap();
+
+        // Each arc carries its weight and the constraint it came from.
+        let mut arcs: Vec<(D, usize)> = Vec::with_capacity(self.constraints.len());
+        let mut graph: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n + 1];
+        let mut add_arc = |u: usize, v: usize, weight: D, k: usize| {
+            graph[u].push((v, arcs.len()));
+            arcs.push((weight, k));
+        };
+        for (k, (&c, &bound)) in self.constraints.iter().zip(&self.bounds).enumerate() {
+            match c {
+                Constraint::LessEq { j, i, .. } | Constraint::Less { j, i, .. } => {
+                    add_arc(index(i), index(j), bound, k)
+                }
+                Constraint::Fixed { var, .. } => {
+                    add_arc(origin, index(var), bound, k);
+                    add_arc(index(var), origin, D::zero() - bound, k);
+                }
+            }
+        }
+
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = vec![D::zero(); n + 1];
+        if let Some(cycle) = ncf.howard(&mut dist, |&a| arcs[a].0) {
+            return Err(Infeasible {
+                constraints: cycle
+                    .payloads()
+                    .map(|&a| self.constraints[arcs[a].1])
+                    .collect(),
+                weight: cycle.weight,
+            });
+        }
+
+        let shift = dist[origin];
+        Ok(self
+            .vars
+            .labels()
+            .iter()
+            .zip(&dist)
+            .map(|(&v, &d)| (v, d - shift))
+            .collect())
+    }
+}
+
+impl<V, D> DifferenceConstraints<V, D>
+where
+    V: Copy + Eq + Hash,
+    D: Add<Output = D> + Sub<Output = D> + PartialOrd + Copy + Zero + Integer,
+{
+    /// Add the strict constraint $x_j - x_i < \text{bound}$.
+    ///
+    /// Only available for integer domains, where it is equivalent to
+    /// $x_j - x_i \le \text{bound} - 1$.
+    pub fn less(&mut self, j: V, i: V, bound: D) -> &mut Self {
+        self.push(Constraint::Less { j, i, bound }, bound - D::one())
+    }
+
+    /// Add the strict constraint $x_j - x_i > \text{bound}$.
+    pub fn greater(&mut self, j: V, i: V, bound: D) -> &mut Self {
+        self.less(i, j, <D as Zero>::zero() - bound)
+    }
+}
+
+impl<V, D> Default for DifferenceConstraints<V, D>
+where
+    V: Copy + Eq + Hash,
+    D: Add<Output = D> + Sub<Output = D> + PartialOrd + Copy + Zero,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfied(x: &HashMap<&str, i32>, c: &Constraint<&str, i32>) -> bool {
+        match *c {
+            Constraint::LessEq { j, i, bound } => x[j] - x[i] <= bound,
+            Constraint::Less { j, i, bound } => x[j] - x[i] < bound,
+            Constraint::Fixed { var, value } => x[var] == value,
+        }
+    }
+
+    #[test]
+    fn test_feasible() {
+        let mut sys = DifferenceConstraints::new();
+        sys.less_eq("b", "a", 3)
+            .less_eq("c", "b", -2)
+            .greater_eq("c", "a", -4)
+            .add_variable("d");
+        let x = sys.solve().unwrap();
+        assert_eq!(x.len(), 4);
+        assert_eq!(x["d"], 0);
+        assert!(sys.constraints().iter().all(|c| satisfied(&x, c)));
+    }
+
+    #[test]
+    fn test_infeasible_certificate() {
+        let mut sys = DifferenceConstraints::new();
+        sys.less_eq("b", "a", 1)
+            .less_eq("c", "b", 1)
+            .less_eq("a", "c", -3)
+            .less_eq("d", "a", 0);
+        let err = sys.solve().unwrap_err();
+        assert_eq!(err.weight, -1);
+        assert_eq!(err.constraints.len(), 3);
+        assert!(!err.constraints.contains(&Constraint::LessEq {
+            j: "d",
+            i: "a",
+            bound: 0
+        }));
+        let total: i32 = err
+            .constraints
+            .iter()
+            .map(|c| match *c {
+                Constraint::LessEq { bound, .. } => bound,
+                _ => unreachable!(),
+            })
+            .sum();
+        assert_eq!(total, err.weight);
+        assert!(err.to_string().contains("3 constraints"));
+    }
+
+    #[test]
+    fn test_strict() {
+        let mut sys = DifferenceConstraints::new();
+        sys.less("b", "a", 0)
+            .less("c", "b", 0)
+            .greater("c", "a", -3);
+        let x = sys.solve().unwrap();
+        assert!(x["c"] < x["b"] && x["b"] < x["a"]);
+        assert!(sys.constraints().iter().all(|c| satisfied(&x, c)));
+
+        // a > b > c > a - 2 has no integer solution
+        sys.less("a", "c", 2);
+        assert!(sys.solve().is_err());
+    }
+
+    #[test]
+    fn test_fixed() {
+        let mut sys = DifferenceConstraints::new();
+        sys.fix("a", 5)
+            .fix("b", 7)
+            .less_eq("c", "a", -1)
+            .less_eq("b", "c", 10);
+        let x = sys.solve().unwrap();
+        assert_eq!((x["a"], x["b"]), (5, 7));
+        assert!(sys.constraints().iter().all(|c| satisfied(&x, c)));
+    }
+
+    #[test]
+    fn test_conflicting_fixed() {
+        let mut sys = DifferenceConstraints::new();
+        sys.fix("a", 0).fix("b", 5).less_eq("b", "a", 3);
+        let err = sys.solve().unwrap_err();
+        assert_eq!(err.weight, -2);
+        assert!(err
+            .constraints
+            .contains(&Constraint::Fixed { var: "b", value: 5 }));
+        assert!(err
+            .constraints
+            .contains(&Constraint::Fixed { var: "a", value: 0 }));
+    }
+
+    #[test]
+    fn test_float_domain() {
+        let mut sys: DifferenceConstraints<u64, f64> = DifferenceConstraints::new();
+        sys.less_eq(1, 0, 0.5).greater_eq(1, 0, 0.25).fix(0, 1.0);
+        let x = sys.solve().unwrap();
+        assert_eq!(x[&0], 1.0);
+        assert!(x[&1] - x[&0] <= 0.5 && x[&1] - x[&0] >= 0.25);
+    }
+
+    #[test]
+    fn test_empty() {
+        let sys: DifferenceConstraints<&str, i32> = DifferenceConstraints::default();
+        assert!(sys.solve().unwrap().is_empty());
+        assert_eq!(sys.num_variables(), 0);
+    }
+}
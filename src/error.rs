@@ -0,0 +1,132 @@
+//! Crate-level error type for the `try_` entry points.
+//!
+//! The plain searches such as [`NegCycleFinder::howard`] treat a node
+//! without a distance as being at zero and rely on internal invariants when
+//! they rebuild cycles.  Their `try_` counterparts check these conditions
+//! instead and report a violation as an [`Error`], so that a caller that
+//! must never panic can handle it.
+//!
+//! [`NegCycleFinder::howard`]: crate::NegCycleFinder::howard
+//!
+//! # Example
+//!
+//! ```rust
+//! use digraphx_rs::{Error, NegCycleFinder};
+//!
+//! let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)]];
+//! let mut ncf = NegCycleFinder::new(&graph);
+//!
+//! let mut short = vec![0; 2];
+//! assert_eq!(ncf.try_howard(&mut short, |w| *w), Err(Error::MissingNode(2)));
+//!
+//! let mut dist = vec![0; 3];
+//! assert_eq!(ncf.try_howard(&mut dist, |w| *w).unwrap().unwrap().weight, -1);
+//! ```
+
+use std::fmt;
+
+use crate::config::{Interrupt, Outcome, SolverConfig};
+use crate::node_map::NodeMap;
+use crate::Graph;
+
+/// Errors reported by the `try_` entry points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error<N> {
+    /// The distance map has no entry for this node.
+    MissingNode(N),
+    /// Adding the weight of the arc `tail -> head` left the range of the
+    /// distance domain.
+    Overflow {
+        /// Tail of the arc.
+        tail: N,
+        /// Head of the arc.
+        head: N,
+    },
+    /// A search stopped after this many relaxation passes, the limit set
+    /// with [`SolverConfig::with_max_passes`], without converging.
+    IterationLimit(usize),
+    /// The predecessor (or successor) chain through this node does not
+    /// close into a cycle, so the graph changed under the search or its
+    /// [`Graph`] implementation is inconsistent.
+    InconsistentGraph(N),
+}
+
+impl<N: fmt::Debug> fmt::Display for Error<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingNode(n) => write!(f, "no distance for node {n:?}"),
+            Error::Overflow { tail, head } => {
+                write!(f, "arithmetic overflow on arc {tail:?} -> {head:?}")
+            }
+            Error::IterationLimit(passes) => {
+                write!(f, "no convergence after {passes} passes")
+            }
+            Error::InconsistentGraph(n) => {
+                write!(f, "inconsistent graph: broken cycle through node {n:?}")
+            }
+        }
+    }
+}
+
+impl<N: fmt::Debug> std::error::Error for Error<N> {}
+
+/// Check that `dist` has an entry for every node of `graph`, including the
+/// nodes that only appear as neighbors.
+pub(crate) fn check_dist<G, D, M>(graph: &G, dist: &M) -> Result<(), Error<G::Node>>
+where
+    G: Graph,
+    M: NodeMap<G::Node, D>,
+{
+    for u in graph.nodes() {
+        if !dist.contains_node(u) {
+            return Err(Error::MissingNode(u));
+        }
+        for (v, _) in graph.neighbors(u) {
+            if !dist.contains_node(v) {
+                return Err(Error::MissingNode(v));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Turn an interruption by the pass limit of `config` into an
+/// [`Error::IterationLimit`]; other interruptions are kept.
+pub(crate) fn check_limit<N, T>(
+    outcome: Outcome<T>,
+    config: &SolverConfig,
+) -> Result<Outcome<T>, Error<N>> {
+    match (outcome.interrupt(), config.max_passes()) {
+        (Some(Interrupt::IterationLimit), Some(passes)) => Err(Error::IterationLimit(passes)),
+        _ => Ok(outcome),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Error::MissingNode(3).to_string(), "no distance for node 3");
+        let e = Error::Overflow {
+            tail: "a",
+            head: "b",
+        };
+        assert!(e.to_string().contains("\"a\" -> \"b\""));
+        assert!(Error::<u8>::IterationLimit(7).to_string().contains('7'));
+        assert!(Error::InconsistentGraph(1).to_string().contains("node 1"));
+    }
+
+    #[test]
+    fn test_check_dist() {
+        let mut graph: HashMap<&str, HashMap<&str, i32>> = HashMap::new();
+        graph.insert("a", [("b", 1)].into());
+        let mut dist: HashMap<&str, i32> = [("a", 0)].into();
+        assert_eq!(check_dist(&graph, &dist), Err(Error::MissingNode("b")));
+        dist.insert("b", 0);
+        assert_eq!(check_dist(&graph, &dist), Ok(()));
+    }
+}
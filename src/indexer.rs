@@ -0,0 +1,406 @@
+//! Compaction of arbitrary node keys to dense `usize` indices.
+//!
+//! Graphs keyed by `&str` or `u64` ids pay for hashing on every lookup.
+//! [`NodeIndexer`] builds a bijection between the nodes of any [`Graph`] and
+//! `0..n`, converts the graph into a `Vec<Vec<(usize, E)>>` or a
+//! [`CsrGraph`], and translates cycles, distances and predecessors computed
+//! on the dense form back to the original keys.  [`IndexedGraph`] bundles
+//! the dense graph with its indexer.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use digraphx_rs::indexer::IndexedGraph;
+//! use digraphx_rs::{graph_from_edges, NegCycleFinder};
+//!
+//! let g = graph_from_edges(&[("a", "b", 1), ("b", "c", 1), ("c", "a", -3)]);
+//! let dense = IndexedGraph::from_graph(&g);
+//!
+//! let mut ncf = NegCycleFinder::new(&dense);
+//! let mut dist = vec![0; dense.indexer().len()];
+//! let cycle = ncf.howard(&mut dist, |w| *w).unwrap();
+//!
+//! let cycle = dense.indexer().cycle(&cycle);
+//! let mut nodes = cycle.nodes.clone();
+//! nodes.sort();
+//! assert_eq!(nodes, vec!["a", "b", "c"]);
+//!
+//! let dist: HashMap<&str, i32> = dense.indexer().distances(&dist);
+//! assert_eq!(dist.len(), 3);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::node_map::NodeMap;
+use crate::node_map::VecMap;
+use crate::{CsrGraph, Cycle, Graph, ShortestPaths};
+
+/// A bijection between node keys `N` and the dense indices `0..len()`.
+///
+/// Indices are assigned in insertion order.
+#[derive(Debug, Clone)]
+pub struct NodeIndexer<N> {
+    labels: Vec<N>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Copy + Eq + Hash> NodeIndexer<N> {
+    /// Create an empty indexer.
+    pub fn new() -> Self {
+        NodeIndexer {
+            labels: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Index every node of `graph`, including nodes that only appear as
+    /// neighbors.
+    ///
+    /// The nodes reported by [`Graph::nodes`] come first, in iteration
+    /// order, followed by neighbor-only nodes in the order they are met.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: Graph<Node = N>,
+    {
+        let mut indexer = Self::new();
+        for u in graph.nodes() {
+            indexer.insert(u);
+        }
+        let mut i = 0;
+        while i < indexer.labels.len() {
+            for (v, _) in graph.neighbors(indexer.labels[i]) {
+                indexer.insert(v);
+            }
+            i += 1;
+        }
+        indexer
+    }
+
+    /// Return the index of `node`, assigning the next free index if it has
+    /// not been seen before.
+    pub fn insert(&mut self, node: N) -> usize {
+        let labels = &mut self.labels;
+        *self.index.entry(node).or_insert_with(|| {
+            labels.push(node);
+            labels.len() - 1
+        })
+    }
+
+    /// Return the index of `node`, or `None` if it has not been indexed.
+    #[inline]
+    pub fn index(&self, node: N) -> Option<usize> {
+        self.index.get(&node).copied()
+    }
+
+    /// Return the node with index `i`, or `None` if `i` is out of range.
+    #[inline]
+    pub fn node(&self, i: usize) -> Option<N> {
+        self.labels.get(i).copied()
+    }
+
+    /// Return the number of indexed nodes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Return `true` if no node has been indexed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Return the indexed nodes; entry `i` is the node with index `i`.
+    #[inline]
+    pub fn labels(&self) -> &[N] {
+        &self.labels
+    }
+
+    /// Consume the indexer and return its labels.
+    #[inline]
+    pub fn into_labels(self) -> Vec<N> {
+        self.labels
+    }
+
+    /// Convert `graph` into an adjacency list over the dense indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `graph` has a node that has not been indexed; an indexer
+    /// built with [`from_graph`](Self::from_graph) on the same graph covers
+    /// every node.
+    pub fn adjacency<G>(&self, graph: &G) -> Vec<Vec<(usize, G::Edge)>>
+    where
+        G: Graph<Node = N>,
+    {
+        self.labels
+            .iter()
+            .map(|&u| {
+                graph
+                    .neighbors(u)
+                    .map(|(v, e)| (self.index[&v], e))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Convert `graph` into a [`CsrGraph`] over the dense indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `graph` has a node that has not been indexed.
+    pub fn csr<G>(&self, graph: &G) -> CsrGraph<G::Edge>
+    where
+        G: Graph<Node = N>,
+    {
+        CsrGraph::from_adjacency(&self.adjacency(graph))
+    }
+
+    /// Translate a cycle over dense indices back to the original nodes.
+    pub fn cycle<E: Copy, D: Copy>(&self, cycle: &Cycle<usize, E, D>) -> Cycle<N, E, D> {
+        Cycle {
+            nodes: cycle.nodes.iter().map(|&i| self.labels[i]).collect(),
+            edges: cycle
+                .edges
+                .iter()
+                .map(|&(u, v, e)| (self.labels[u], self.labels[v], e))
+                .collect(),
+            weight: cycle.weight,
+        }
+    }
+
+    /// Translate a distance map over dense indices, such as a `Vec<D>`,
+    /// back to the original nodes.  Indices without a value are skipped.
+    pub fn distances<D, M>(&self, dist: &M) -> HashMap<N, D>
+    where
+        D: Copy,
+        M: NodeMap<usize, D>,
+    {
+        self.labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &u)| dist.get(i).map(|&d| (u, d)))
+            .collect()
+    }
+
+    /// Translate a predecessor map over dense indices back to the original
+    /// nodes.  Indices without a predecessor are skipped.
+    pub fn predecessors<E, P>(&self, pred: &P) -> HashMap<N, (N, E)>
+    where
+        E: Copy,
+        P: NodeMap<usize, (usize, E)>,
+    {
+        self.labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &v)| pred.get(i).map(|&(u, e)| (v, (self.labels[u], e))))
+            .collect()
+    }
+
+    /// Translate single-source shortest paths over dense indices back to the
+    /// original nodes.
+    pub fn shortest_paths<E, D, DM, PM>(
+        &self,
+        paths: &ShortestPaths<usize, E, D, DM, PM>,
+    ) -> ShortestPaths<N, E, D>
+    where
+        E: Copy,
+        D: Copy,
+        DM: NodeMap<usize, D>,
+        PM: NodeMap<usize, (usize, E)>,
+    {
+        ShortestPaths::new(
+            self.labels[paths.source],
+            self.distances(&paths.distances),
+            self.predecessors(&paths.predecessors),
+        )
+    }
+}
+
+impl<N: Copy + Eq + Hash> Default for NodeIndexer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A dense graph over `0..n` together with the [`NodeIndexer`] that maps its
+/// nodes back to the keys of the graph it was built from.
+///
+/// `IndexedGraph` implements [`Graph`] with `usize` nodes by delegating to
+/// the dense graph, so it can be handed to any algorithm directly.
+#[derive(Debug, Clone)]
+pub struct IndexedGraph<N, G> {
+    indexer: NodeIndexer<N>,
+    graph: G,
+}
+
+impl<N: Copy + Eq + Hash, E: Copy> IndexedGraph<N, Vec<Vec<(usize, E)>>> {
+    /// Compact `graph` into an adjacency list over dense indices.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: Graph<Node = N, Edge = E>,
+    {
+        let indexer = NodeIndexer::from_graph(graph);
+        let graph = indexer.adjacency(graph);
+        IndexedGraph { indexer, graph }
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Copy> IndexedGraph<N, CsrGraph<E>> {
+    /// Compact `graph` into a [`CsrGraph`] over dense indices.
+    pub fn csr_from_graph<G>(graph: &G) -> Self
+    where
+        G: Graph<Node = N, Edge = E>,
+    {
+        let indexer = NodeIndexer::from_graph(graph);
+        let graph = indexer.csr(graph);
+        IndexedGraph { indexer, graph }
+    }
+}
+
+impl<N, G> IndexedGraph<N, G> {
+    /// Return the node indexer.
+    #[inline]
+    pub fn indexer(&self) -> &NodeIndexer<N> {
+        &self.indexer
+    }
+
+    /// Return the dense graph.
+    #[inline]
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Consume the wrapper and return the dense graph and its indexer.
+    #[inline]
+    pub fn into_parts(self) -> (G, NodeIndexer<N>) {
+        (self.graph, self.indexer)
+    }
+}
+
+impl<N, G> Graph for IndexedGraph<N, G>
+where
+    G: Graph<Node = usize>,
+{
+    type Node = usize;
+    type Edge = G::Edge;
+    type Nodes<'a>
+        = G::Nodes<'a>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = G::Neighbors<'a>
+    where
+        Self: 'a;
+    type Map<V> = VecMap<V>;
+
+    #[inline]
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.graph.nodes()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        self.graph.neighbors(node)
+    }
+
+    #[inline]
+    fn num_nodes(&self) -> usize {
+        self.graph.num_nodes()
+    }
+
+    #[inline]
+    fn node_map<V>(&self) -> VecMap<V> {
+        VecMap::with_capacity(self.graph.num_nodes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bellman_ford, graph_from_edges, NegCycleFinder};
+
+    #[test]
+    fn test_bijection() {
+        let g = graph_from_edges(&[("a", "b", 1), ("b", "c", 2)]);
+        let ix = NodeIndexer::from_graph(&g);
+        assert_eq!(ix.len(), 3);
+        for (i, &u) in ix.labels().iter().enumerate() {
+            assert_eq!(ix.index(u), Some(i));
+            assert_eq!(ix.node(i), Some(u));
+        }
+        assert_eq!(ix.index("z"), None);
+        assert_eq!(ix.node(3), None);
+    }
+
+    #[test]
+    fn test_insert_is_idempotent() {
+        let mut ix = NodeIndexer::new();
+        assert!(ix.is_empty());
+        assert_eq!(ix.insert(10u64), 0);
+        assert_eq!(ix.insert(20), 1);
+        assert_eq!(ix.insert(10), 0);
+        assert_eq!(ix.into_labels(), vec![10, 20]);
+    }
+
+    #[test]
+    fn test_adjacency_preserves_edges() {
+        let g = graph_from_edges(&[(7u64, 3, 1), (3, 9, 2), (9, 7, -4)]);
+        let ix = NodeIndexer::from_graph(&g);
+        let adj = ix.adjacency(&g);
+        let csr = ix.csr(&g);
+        let mut count = 0;
+        for u in 0..ix.len() {
+            for &(v, w) in &adj[u] {
+                assert_eq!(g[&ix.labels()[u]][&ix.labels()[v]], w);
+                count += 1;
+            }
+            assert_eq!(
+                csr.neighbors(u).collect::<Vec<_>>(),
+                adj.neighbors(u).collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_cycle_round_trip() {
+        let g = graph_from_edges(&[("x", "y", 2), ("y", "z", 1), ("z", "x", -5), ("w", "x", 0)]);
+        let dense = IndexedGraph::csr_from_graph(&g);
+        let mut ncf = NegCycleFinder::new(&dense);
+        let mut dist = vec![0; dense.num_nodes()];
+        let cycle = dense
+            .indexer()
+            .cycle(&ncf.howard(&mut dist, |w| *w).unwrap());
+        assert_eq!(cycle.weight, -2);
+        for (i, &(u, v, w)) in cycle.edges.iter().enumerate() {
+            assert_eq!(u, cycle.nodes[i]);
+            assert_eq!(v, cycle.nodes[(i + 1) % cycle.len()]);
+            assert_eq!(g[&u][&v], w);
+        }
+        let dist = dense.indexer().distances(&dist);
+        assert_eq!(dist.len(), 4);
+    }
+
+    #[test]
+    fn test_shortest_paths_round_trip() {
+        let g = graph_from_edges(&[("s", "a", 4), ("s", "b", 1), ("b", "a", 2)]);
+        let dense = IndexedGraph::from_graph(&g);
+        let s = dense.indexer().index("s").unwrap();
+        let paths = bellman_ford(&dense, s, |w| *w).unwrap();
+        let paths = dense.indexer().shortest_paths(&paths);
+        assert_eq!(paths.distance("a"), Some(3));
+        assert_eq!(paths.path_to("a"), Some(vec!["s", "b", "a"]));
+        assert_eq!(paths.predecessors["a"], ("b", 2));
+    }
+
+    #[test]
+    fn test_into_parts() {
+        let g = graph_from_edges(&[(1u64, 2, 1.0)]);
+        let (adj, ix) = IndexedGraph::from_graph(&g).into_parts();
+        assert_eq!(adj.len(), 2);
+        assert_eq!(ix.len(), 2);
+    }
+}
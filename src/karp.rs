@@ -0,0 +1,258 @@
+//! Karp's minimum mean cycle algorithm.
+//!
+//! For a graph with $n$ nodes, let $D_k(v)$ be the minimum weight of a walk
+//! with exactly $k$ arcs ending at $v$ (starting anywhere).  Karp's theorem
+//! states that the minimum cycle mean is
+//!
+//! $$ \mu^* = \min_{v} \max_{0 \le k < n} \frac{D_n(v) - D_k(v)}{n - k} $$
+//!
+//! and every cycle on a minimum $n$-arc walk to the minimizing node is a
+//! minimum mean cycle.  Unlike [`MaxParametricSolver`](crate::MaxParametricSolver),
+//! which repeats Howard's algorithm until the ratio stops improving, this
+//! takes a fixed $O(nm)$ time on every input.
+
+use std::ops::{Add, Div, Sub};
+
+use num::FromPrimitive;
+
+use crate::indexer::NodeIndexer;
+use crate::{Cycle, Graph, Zero};
+
+/// A minimum mean cycle together with its mean.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeanCycle<N, E, D> {
+    /// Mean weight of the cycle, `cycle.weight / cycle.len()`.
+    pub mean: D,
+    /// The critical cycle.
+    pub cycle: Cycle<N, E, D>,
+}
+
+/// Find a minimum mean cycle with Karp's algorithm.
+///
+/// Edge payloads are mapped into the distance domain `D` by `get_weight`.
+/// `D` must support exact division by an arc count for the mean to be
+/// exact, so integer weights should be lifted into
+/// `num::rational::Ratio`.  Returns `None` if the graph is acyclic.
+///
+/// Complexity: $O(nm)$ time and $O(n^2)$ space.
+///
+/// # Example
+///
+/// ```rust
+/// use num::rational::Ratio;
+/// use digraphx_rs::{graph_from_edges, min_mean_cycle};
+///
+/// let graph = graph_from_edges(&[("a", "b", 3), ("b", "a", 2), ("b", "c", 1), ("c", "b", 1)]);
+/// let mc = min_mean_cycle(&graph, |w| Ratio::from_integer(*w)).unwrap();
+/// assert_eq!(mc.mean, Ratio::from_integer(1));
+/// assert_eq!(mc.cycle.len(), 2);
+/// assert!(mc.cycle.nodes.contains(&"c"));
+/// ```
+pub fn min_mean_cycle<G, D, F>(graph: &G, get_weight: F) -> Option<MeanCycle<G::Node, G::Edge, D>>
+where
+    G: Graph,
+    D: Add<Output = D>
+        + Sub<Output = D>
+        + Div<Output = D>
+        + PartialOrd
+        + Copy
+        + Zero
+        + FromPrimitive,
+    F: Fn(&G::Edge) -> D,
+{
+    let indexer = NodeIndexer::from_graph(graph);
+    let adj = indexer.adjacency(graph);
+    let n = adj.len();
+    if n == 0 {
+        return None;
+    }
+
+    // dist[k][v]: minimum weight of a k-arc walk ending at v, if any;
+    // pred[k][v]: the last arc of such a walk.
+    let mut dist: Vec<Vec<Option<D>>> = Vec::with_capacity(n + 1);
+    let mut pred = Vec::with_capacity(n + 1);
+    dist.push(vec![Some(D::zero()); n]);
+    pred.push(vec![None; n]);
+    for k in 0..n {
+        let mut dk = vec![None; n];
+        let mut pk = vec![None; n];
+        for (u, nbrs) in adj.iter().enumerate() {
+            let Some(du) = dist[k][u] else {
+                continue;
+            };
+            for &(v, e) in nbrs {
+                let d = du + get_weight(&e);
+                if dk[v].map_or(true, |dv| d < dv) {
+                    dk[v] = Some(d);
+                    pk[v] = Some((u, e));
+                }
+            }
+        }
+        dist.push(dk);
+        pred.push(pk);
+    }
+
+    // Karp's formula; nodes with no n-arc walk do not lie downstream of a
+    // cycle and are skipped.
+    let mut best: Option<(D, usize)> = None;
+    for (v, &dn) in dist[n].iter().enumerate() {
+        let Some(dn) = dn else {
+            continue;
+        };
+        let worst = (0..n)
+            .filter_map(|k| dist[k][v].map(|dk| (dn - dk) / D::from_usize(n - k).unwrap()))
+            .fold(None, |acc: Option<D>, m| match acc {
+                Some(w) if w >= m => Some(w),
+                _ => Some(m),
+            })
+            .unwrap();
+        if best.map_or(true, |(b, _)| worst < b) {
+            best = Some((worst, v));
+        }
+    }
+    let (mean, v) = best?;
+
+    // Walk back along the minimum n-arc walk to v until a node repeats.
+    let mut seen = vec![None; n];
+    let mut walk = Vec::with_capacity(n);
+    let mut vtx = v;
+    let mut k = n;
+    while seen[vtx].is_none() {
+        seen[vtx] = Some(walk.len());
+        let (u, e) = pred[k][vtx].unwrap();
+        walk.push((u, vtx, e));
+        vtx = u;
+        k -= 1;
+    }
+    let start = seen[vtx].unwrap();
+    let mut edges: Vec<(usize, usize, G::Edge)> = walk.split_off(start);
+    edges.reverse();
+
+    let labels = indexer.labels();
+    let edges: Vec<(G::Node, G::Node, G::Edge)> = edges
+        .into_iter()
+        .map(|(u, v, e)| (labels[u], labels[v], e))
+        .collect();
+    let weight = edges
+        .iter()
+        .fold(D::zero(), |acc, (_, _, e)| acc + get_weight(e));
+    let nodes = edges.iter().map(|&(u, _, _)| u).collect();
+    Some(MeanCycle {
+        mean,
+        cycle: Cycle {
+            nodes,
+            edges,
+            weight,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_from_edges;
+    use crate::parametric::{MaxParametricSolver, ParametricAPI};
+    use crate::testing::{random_edges, Lcg};
+    use num::rational::Ratio;
+    use std::collections::HashMap;
+
+    fn ratio(w: &i32) -> Ratio<i32> {
+        Ratio::from_integer(*w)
+    }
+
+    fn check_cycle(mc: &MeanCycle<u32, i32, Ratio<i32>>, graph: &HashMap<u32, HashMap<u32, i32>>) {
+        let c = &mc.cycle;
+        assert!(!c.is_empty());
+        for (i, &(u, v, w)) in c.edges.iter().enumerate() {
+            assert_eq!(u, c.nodes[i]);
+            assert_eq!(v, c.nodes[(i + 1) % c.len()]);
+            assert_eq!(graph[&u][&v], w);
+        }
+        assert_eq!(c.weight / Ratio::from_integer(c.len() as i32), mc.mean);
+    }
+
+    #[test]
+    fn test_single_cycle() {
+        let graph = graph_from_edges(&[(0u32, 1, 1), (1, 2, 2), (2, 0, 4)]);
+        let mc = min_mean_cycle(&graph, ratio).unwrap();
+        assert_eq!(mc.mean, Ratio::new(7, 3));
+        check_cycle(&mc, &graph);
+    }
+
+    #[test]
+    fn test_picks_minimum() {
+        // Means: 0 -> 1 -> 0 is 5/2, 1 -> 2 -> 3 -> 1 is 1, 3 -> 3 is 3
+        let graph = graph_from_edges(&[
+            (0u32, 1, 2),
+            (1, 0, 3),
+            (1, 2, 0),
+            (2, 3, 1),
+            (3, 1, 2),
+            (3, 3, 3),
+        ]);
+        let mc = min_mean_cycle(&graph, ratio).unwrap();
+        assert_eq!(mc.mean, Ratio::from_integer(1));
+        assert_eq!(mc.cycle.len(), 3);
+        check_cycle(&mc, &graph);
+    }
+
+    #[test]
+    fn test_negative_mean() {
+        let graph = graph_from_edges(&[(0u32, 1, 1), (1, 0, -4), (1, 2, 5)]);
+        let mc = min_mean_cycle(&graph, ratio).unwrap();
+        assert_eq!(mc.mean, Ratio::new(-3, 2));
+        check_cycle(&mc, &graph);
+    }
+
+    #[test]
+    fn test_acyclic() {
+        let graph = graph_from_edges(&[(0u32, 1, 1), (1, 2, 1), (0, 2, 5)]);
+        assert!(min_mean_cycle(&graph, ratio).is_none());
+        let empty: HashMap<u32, HashMap<u32, i32>> = HashMap::new();
+        assert!(min_mean_cycle(&empty, ratio).is_none());
+    }
+
+    #[test]
+    fn test_float_weights() {
+        let graph: Vec<Vec<(usize, f64)>> = vec![vec![(1, 1.5)], vec![(0, 0.5), (1, 1.25)]];
+        let mc = min_mean_cycle(&graph, |w| *w).unwrap();
+        assert_eq!(mc.mean, 1.0);
+        assert_eq!(mc.cycle.len(), 2);
+    }
+
+    struct MeanRatio;
+
+    impl ParametricAPI<i32, Ratio<i32>> for MeanRatio {
+        fn distance(&self, r: &Ratio<i32>, w: &i32) -> Ratio<i32> {
+            Ratio::from_integer(*w) - *r
+        }
+        fn zero_cancel(&self, cycle: &[i32]) -> Ratio<i32> {
+            Ratio::new(cycle.iter().sum(), cycle.len() as i32)
+        }
+    }
+
+    #[test]
+    fn test_matches_parametric_solver() {
+        let mut rng = Lcg(42);
+        for _ in 0..50 {
+            let edges = random_edges(&mut rng, 8, |rng| rng.next(21) as i32 - 10);
+            let graph = graph_from_edges(&edges);
+
+            let karp = min_mean_cycle(&graph, ratio);
+            let mut solver = MaxParametricSolver::new(&graph, MeanRatio);
+            let dist: HashMap<u32, Ratio<i32>> = HashMap::new();
+            let result = solver.run(dist, Ratio::from_integer(100));
+            let r = result.ratio;
+            let param = result.cycle;
+
+            match karp {
+                Some(mc) => {
+                    assert!(param.is_some());
+                    assert_eq!(mc.mean, r);
+                    check_cycle(&mc, &graph);
+                }
+                None => assert!(param.is_none()),
+            }
+        }
+    }
+}
@@ -28,18 +28,38 @@
 //! assert!(cycle.is_some());
 //! ```
 
+#[macro_use]
+mod macros;
+
+pub mod compare;
+pub mod config;
+pub mod csr;
+pub mod cycle;
+pub mod cycle_ratio;
+pub mod difference_constraints;
+pub mod error;
+pub mod indexer;
+pub mod karp;
 pub mod map_adapter;
 pub mod neg_cycle;
+pub mod node_map;
+pub mod observer;
 pub mod parametric;
 
 #[cfg(feature = "std")]
 pub mod logging;
 
 pub mod prelude;
+pub mod reverse;
+pub mod shortest_path;
+pub mod views;
 
+#[cfg(test)]
+mod testing;
+
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::ops::Add;
 use std::ops::Range;
 
 use crate::map_adapter::MapAdapter;
@@ -52,14 +72,20 @@ use crate::map_adapter::MapAdapter;
 ///
 /// The basic assumption is that a graph maps each node to a container of its
 /// outgoing edges.  Iterating over the outer container yields node–neighbors
-/// pairs; iterating over the inner container yields (neighbor, weight) pairs.
+/// pairs; iterating over the inner container yields (neighbor, edge) pairs.
+///
+/// The node and neighbor iterators are generic associated types that borrow
+/// from the graph, so walking the adjacency of a node does not allocate.
 ///
 /// # Provided implementations
 ///
-/// | Container                          | Node    | Weight |
+/// | Container                          | Node    | Edge   |
 /// |------------------------------------|---------|--------|
 /// | `HashMap<N, HashMap<N, W>>`       | `N`     | `W`    |
 /// | `BTreeMap<N, BTreeMap<N, W>>`     | `N`     | `W`    |
+/// | `Vec<Vec<(usize, W)>>`            | `usize` | `W`    |
+/// | `MapAdapter<Vec<(usize, W)>>`     | `usize` | `W`    |
+/// | [`CsrGraph<W>`](crate::CsrGraph)  | `usize` | `W`    |
 ///
 /// # Example
 ///
@@ -75,23 +101,122 @@ pub trait Graph {
     /// Identifier for a node (must be copyable, comparable, hashable).
     type Node: Copy + Eq + Hash;
 
-    /// Edge weight (must support addition and ordering).
-    type Weight: Copy + Add<Output = Self::Weight> + PartialOrd;
+    /// Edge payload (the data stored on each edge).
+    ///
+    /// The payload need not be numeric: the algorithms map it into their
+    /// distance domain through a `get_weight` closure, so an edge may carry a
+    /// struct with a cost, a transit time and an identifier.
+    type Edge: Copy;
 
-    /// Iterator over node identifiers.
-    type Nodes: IntoIterator<Item = Self::Node>;
+    /// Iterator over node identifiers, borrowing from the graph.
+    type Nodes<'a>: Iterator<Item = Self::Node>
+    where
+        Self: 'a;
+
+    /// Iterator over (neighbor, edge) pairs for a given node, borrowing from
+    /// the graph.
+    type Neighbors<'a>: Iterator<Item = (Self::Node, Self::Edge)>
+    where
+        Self: 'a;
 
-    /// Iterator over (neighbor, weight) pairs for a given node.
-    type Neighbors: IntoIterator<Item = (Self::Node, Self::Weight)>;
+    /// Node-indexed storage used by the algorithms for per-node state such
+    /// as predecessors.
+    ///
+    /// Index-based graphs use a [`VecMap`]; other graphs fall back to a
+    /// `HashMap` (or `BTreeMap`) keyed by node.
+    type Map<V>: NodeMap<Self::Node, V>;
 
     /// Return all nodes in the graph.
-    fn nodes(&self) -> Self::Nodes;
+    fn nodes(&self) -> Self::Nodes<'_>;
 
     /// Return an iterator over the outgoing edges of `node`.
-    fn neighbors(&self, node: Self::Node) -> Self::Neighbors;
+    ///
+    /// The iterator borrows the graph's own storage, so no allocation takes
+    /// place per call.
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_>;
 
     /// Return the number of nodes.
     fn num_nodes(&self) -> usize;
+
+    /// Return an empty [`Map`](Self::Map) sized for this graph.
+    fn node_map<V>(&self) -> Self::Map<V>;
+}
+
+/// A [`Graph`] that can also enumerate the incoming edges of a node.
+///
+/// The container-of-containers layout only stores outgoing edges, so this
+/// capability is optional.  [`Reversed`] materialises it for any `Graph`.
+///
+/// # Example
+///
+/// ```rust
+/// use digraphx_rs::{ReverseGraph, Reversed};
+///
+/// let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 4)], vec![(2, 5)], vec![(1, 6)]];
+/// let r = Reversed::new(&g);
+/// assert_eq!(r.in_neighbors(1).collect::<Vec<_>>(), vec![(2, 5)]);
+/// ```
+pub trait ReverseGraph: Graph {
+    /// Iterator over (predecessor, edge) pairs for a given node, borrowing
+    /// from the graph.
+    type InNeighbors<'a>: Iterator<Item = (Self::Node, Self::Edge)>
+    where
+        Self: 'a;
+
+    /// Return an iterator over the incoming edges of `node`, as pairs of
+    /// the edge's source and payload.
+    fn in_neighbors(&self, node: Self::Node) -> Self::InNeighbors<'_>;
+}
+
+// --- &G ----------------------------------------------------------------------
+//
+// A borrowed graph is a graph, so that adapters such as `Reversed` can wrap a
+// reference instead of taking the graph by value.
+
+impl<G: Graph + ?Sized> Graph for &G {
+    type Node = G::Node;
+    type Edge = G::Edge;
+    type Nodes<'a>
+        = G::Nodes<'a>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = G::Neighbors<'a>
+    where
+        Self: 'a;
+    type Map<V> = G::Map<V>;
+
+    #[inline]
+    fn nodes(&self) -> Self::Nodes<'_> {
+        (**self).nodes()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_> {
+        (**self).neighbors(node)
+    }
+
+    #[inline]
+    fn num_nodes(&self) -> usize {
+        (**self).num_nodes()
+    }
+
+    #[inline]
+    fn node_map<V>(&self) -> Self::Map<V> {
+        (**self).node_map()
+    }
+}
+
+impl<G: ReverseGraph + ?Sized> ReverseGraph for &G {
+    type InNeighbors<'a>
+        = G::InNeighbors<'a>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn in_neighbors(&self, node: Self::Node) -> Self::InNeighbors<'_> {
+        (**self).in_neighbors(node)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -120,68 +245,110 @@ where
     g
 }
 
+/// Iterator over the (neighbor, edge) pairs of a map-based inner container.
+///
+/// Copies each entry out of the borrowed inner map, and yields nothing for a
+/// node that has no entry in the outer map.
+#[derive(Debug, Clone)]
+pub struct MapNeighbors<I> {
+    iter: Option<I>,
+}
+
+impl<'a, N, W, I> Iterator for MapNeighbors<I>
+where
+    N: Copy + 'a,
+    W: Copy + 'a,
+    I: Iterator<Item = (&'a N, &'a W)>,
+{
+    type Item = (N, W);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.as_mut()?.next().map(|(&k, &v)| (k, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.as_ref().map_or((0, Some(0)), |it| it.size_hint())
+    }
+}
+
 // --- HashMap<N, HashMap<N, W>> -------------------------------------------
 
 impl<N, W> Graph for HashMap<N, HashMap<N, W>>
 where
     N: Copy + Eq + Hash,
-    W: Copy + Add<Output = W> + PartialOrd,
+    W: Copy,
 {
     type Node = N;
-    type Weight = W;
-    type Nodes = std::vec::IntoIter<N>;
-    type Neighbors = std::vec::IntoIter<(N, W)>;
-
-    fn nodes(&self) -> Self::Nodes {
-        self.keys().copied().collect::<Vec<_>>().into_iter()
-    }
-
-    fn neighbors(&self, node: N) -> Self::Neighbors {
-        match self.get(&node) {
-            Some(nbrs) => nbrs
-                .iter()
-                .map(|(&k, &v)| (k, v))
-                .collect::<Vec<_>>()
-                .into_iter(),
-            None => Vec::new().into_iter(),
+    type Edge = W;
+    type Nodes<'a>
+        = std::iter::Copied<std::collections::hash_map::Keys<'a, N, HashMap<N, W>>>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = MapNeighbors<std::collections::hash_map::Iter<'a, N, W>>
+    where
+        Self: 'a;
+
+    type Map<V> = HashMap<N, V>;
+
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.keys().copied()
+    }
+
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_> {
+        MapNeighbors {
+            iter: self.get(&node).map(|nbrs| nbrs.iter()),
         }
     }
 
     fn num_nodes(&self) -> usize {
         self.len()
     }
+
+    fn node_map<V>(&self) -> Self::Map<V> {
+        HashMap::with_capacity(self.len())
+    }
 }
 
 // --- BTreeMap<N, BTreeMap<N, W>> -----------------------------------------
 
-impl<N, W> Graph for std::collections::BTreeMap<N, std::collections::BTreeMap<N, W>>
+impl<N, W> Graph for BTreeMap<N, BTreeMap<N, W>>
 where
     N: Copy + Eq + Hash + Ord,
-    W: Copy + Add<Output = W> + PartialOrd,
+    W: Copy,
 {
     type Node = N;
-    type Weight = W;
-    type Nodes = std::vec::IntoIter<N>;
-    type Neighbors = std::vec::IntoIter<(N, W)>;
-
-    fn nodes(&self) -> Self::Nodes {
-        self.keys().copied().collect::<Vec<_>>().into_iter()
-    }
-
-    fn neighbors(&self, node: N) -> Self::Neighbors {
-        match self.get(&node) {
-            Some(nbrs) => nbrs
-                .iter()
-                .map(|(&k, &v)| (k, v))
-                .collect::<Vec<_>>()
-                .into_iter(),
-            None => Vec::new().into_iter(),
+    type Edge = W;
+    type Nodes<'a>
+        = std::iter::Copied<std::collections::btree_map::Keys<'a, N, BTreeMap<N, W>>>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = MapNeighbors<std::collections::btree_map::Iter<'a, N, W>>
+    where
+        Self: 'a;
+
+    type Map<V> = BTreeMap<N, V>;
+
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.keys().copied()
+    }
+
+    fn neighbors(&self, node: N) -> Self::Neighbors<'_> {
+        MapNeighbors {
+            iter: self.get(&node).map(|nbrs| nbrs.iter()),
         }
     }
 
     fn num_nodes(&self) -> usize {
         self.len()
     }
+
+    fn node_map<V>(&self) -> Self::Map<V> {
+        BTreeMap::new()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -225,30 +392,41 @@ where
 // vector stores (neighbor, weight) pairs.  Matches the C++ convention of
 // `vector<vector<pair<size_t, W>>>` wrapped by `MapAdapter`.
 
-#[allow(clippy::unnecessary_to_owned)]
 impl<W> Graph for Vec<Vec<(usize, W)>>
 where
-    W: Copy + Add<Output = W> + PartialOrd,
+    W: Copy,
 {
     type Node = usize;
-    type Weight = W;
-    type Nodes = Range<usize>;
-    type Neighbors = std::vec::IntoIter<(usize, W)>;
+    type Edge = W;
+    type Nodes<'a>
+        = Range<usize>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = std::iter::Copied<std::slice::Iter<'a, (usize, W)>>
+    where
+        Self: 'a;
 
-    fn nodes(&self) -> Self::Nodes {
+    type Map<V> = VecMap<V>;
+
+    fn nodes(&self) -> Self::Nodes<'_> {
         0..self.len()
     }
 
-    fn neighbors(&self, node: usize) -> Self::Neighbors {
-        match self.get(node) {
-            Some(nbrs) => nbrs.to_vec().into_iter(),
-            None => Vec::new().into_iter(),
-        }
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        self.get(node)
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .copied()
     }
 
     fn num_nodes(&self) -> usize {
         self.len()
     }
+
+    fn node_map<V>(&self) -> Self::Map<V> {
+        VecMap::with_capacity(self.len())
+    }
 }
 
 // --- MapAdapter<Vec<(usize, W)>> -------------------------------------------
@@ -258,30 +436,42 @@ where
 // provides the graph interface.  Here `MapAdapter<Vec<(usize, W)>>` stores
 // the adjacency list in its inner Vec (`lst: Vec<Vec<(usize, W)>>`).
 
-#[allow(clippy::unnecessary_to_owned)]
 impl<W> Graph for MapAdapter<Vec<(usize, W)>>
 where
-    W: Copy + Add<Output = W> + PartialOrd,
+    W: Copy,
 {
     type Node = usize;
-    type Weight = W;
-    type Nodes = Range<usize>;
-    type Neighbors = std::vec::IntoIter<(usize, W)>;
+    type Edge = W;
+    type Nodes<'a>
+        = Range<usize>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = std::iter::Copied<std::slice::Iter<'a, (usize, W)>>
+    where
+        Self: 'a;
+
+    type Map<V> = VecMap<V>;
 
-    fn nodes(&self) -> Self::Nodes {
+    fn nodes(&self) -> Self::Nodes<'_> {
         0..self.len()
     }
 
-    fn neighbors(&self, node: usize) -> Self::Neighbors {
-        match self.lst.get(node) {
-            Some(nbrs) => nbrs.to_vec().into_iter(),
-            None => Vec::new().into_iter(),
-        }
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        self.lst
+            .get(node)
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .copied()
     }
 
     fn num_nodes(&self) -> usize {
         self.len()
     }
+
+    fn node_map<V>(&self) -> Self::Map<V> {
+        VecMap::with_capacity(self.len())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -317,18 +507,26 @@ pub mod petgraph_adapter {
     impl<'a, V, E> Graph for PetGraph<'a, V, E>
     where
         V: 'a,
-        E: Copy + Add<Output = E> + PartialOrd + 'a,
+        E: Copy + 'a,
     {
         type Node = NodeIndex;
-        type Weight = E;
-        type Nodes = std::vec::IntoIter<NodeIndex>;
-        type Neighbors = PetNeighbors<'a, E>;
-
-        fn nodes(&self) -> Self::Nodes {
-            self.0.node_indices().collect::<Vec<_>>().into_iter()
+        type Edge = E;
+        type Nodes<'b>
+            = petgraph::graph::NodeIndices
+        where
+            Self: 'b;
+        type Neighbors<'b>
+            = PetNeighbors<'a, E>
+        where
+            Self: 'b;
+
+        type Map<T> = HashMap<NodeIndex, T>;
+
+        fn nodes(&self) -> Self::Nodes<'_> {
+            self.0.node_indices()
         }
 
-        fn neighbors(&self, node: NodeIndex) -> Self::Neighbors {
+        fn neighbors(&self, node: NodeIndex) -> Self::Neighbors<'_> {
             PetNeighbors {
                 iter: self.0.edges(node),
             }
@@ -337,6 +535,10 @@ pub mod petgraph_adapter {
         fn num_nodes(&self) -> usize {
             self.0.node_count()
         }
+
+        fn node_map<T>(&self) -> Self::Map<T> {
+            HashMap::with_capacity(self.0.node_count())
+        }
     }
 
     /// Iterator over petgraph edges, yielding (neighbor, weight).
@@ -372,6 +574,11 @@ impl Zero for i32 {
         0
     }
 }
+impl Zero for i64 {
+    fn zero() -> Self {
+        0
+    }
+}
 impl Zero for f32 {
     fn zero() -> Self {
         0.0
@@ -391,16 +598,56 @@ impl<T: num::Integer + Clone> Zero for num::rational::Ratio<T> {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Logging bound
+// ---------------------------------------------------------------------------
+
+/// Bound on values that the algorithms log, such as the parametric ratio.
+///
+/// With the `std` or `tracing` feature this is [`fmt::Debug`](std::fmt::Debug),
+/// so the values can be written to log records and span fields; without
+/// either feature nothing is logged and every type satisfies it.
+#[cfg(any(feature = "std", feature = "tracing"))]
+pub trait LogDebug: std::fmt::Debug {}
+
+#[cfg(any(feature = "std", feature = "tracing"))]
+impl<T: std::fmt::Debug> LogDebug for T {}
+
+/// Bound on values that the algorithms log, such as the parametric ratio.
+///
+/// With the `std` or `tracing` feature this is [`fmt::Debug`](std::fmt::Debug),
+/// so the values can be written to log records and span fields; without
+/// either feature nothing is logged and every type satisfies it.
+#[cfg(not(any(feature = "std", feature = "tracing")))]
+pub trait LogDebug {}
+
+#[cfg(not(any(feature = "std", feature = "tracing")))]
+impl<T> LogDebug for T {}
+
 // ---------------------------------------------------------------------------
 // Re-exports
 // ---------------------------------------------------------------------------
 
+pub use compare::{Compare, Exact, Tolerance};
+pub use config::{Interrupt, Outcome, SolverConfig};
+pub use csr::CsrGraph;
+pub use cycle::Cycle;
+pub use cycle_ratio::{max_cycle_ratio, min_cycle_ratio, RatioCycle, ZeroTimeCycle};
+pub use difference_constraints::{DifferenceConstraints, Infeasible};
+pub use error::Error;
+pub use indexer::{IndexedGraph, NodeIndexer};
+pub use karp::{min_mean_cycle, MeanCycle};
 pub use neg_cycle::NegCycleFinder;
 pub use neg_cycle::NegCycleFinderQ;
-pub use parametric::{MaxParametricSolver, ParametricAPI};
-
-/// Cycle type: a sequence of node IDs.
-pub type Cycle<N> = Vec<N>;
+pub use node_map::{NodeMap, VecMap};
+pub use observer::{NoObserver, Observer, Stats};
+pub use parametric::{
+    MaxCycleRatio, MaxParametricSolver, MaxParametricSolverQ, MinCostToTimeRatio, MinMeanCycle,
+    ParametricAPI, Relaxation,
+};
+pub use reverse::Reversed;
+pub use shortest_path::{bellman_ford, ShortestPathError, ShortestPaths};
+pub use views::{FilterEdges, FilterNodes, InducedSubgraph, MapWeights};
 
 #[cfg(test)]
 mod tests {
@@ -423,6 +670,28 @@ mod tests {
         assert_eq!(nbrs, vec![("b", 5)]);
     }
 
+    #[test]
+    fn test_graph_trait_hashmap_missing_node() {
+        let g: HashMap<&str, HashMap<&str, i32>> = [("a", [("b", 5)].into())].into();
+        let nbrs = g.neighbors("z");
+        assert_eq!(nbrs.size_hint(), (0, Some(0)));
+        assert_eq!(nbrs.count(), 0);
+    }
+
+    #[test]
+    fn test_graph_trait_btreemap() {
+        let g: BTreeMap<i32, BTreeMap<i32, i32>> = [
+            (0, [(1, 1), (2, 4)].into()),
+            (1, [(2, 2)].into()),
+            (2, BTreeMap::new()),
+        ]
+        .into();
+        assert_eq!(g.num_nodes(), 3);
+        assert_eq!(g.nodes().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![(1, 1), (2, 4)]);
+        assert!(g.neighbors(7).next().is_none());
+    }
+
     #[test]
     fn test_graph_from_edges() {
         let g = graph_from_edges(&[(0, 1, 1), (1, 2, 2), (2, 0, -3)]);
@@ -12,19 +12,49 @@
 //! log::info!("Application started");
 //! ```
 //!
-//! Or with custom filter:
+//! Or with custom filter, using `env_logger`'s directive syntax to set the
+//! level per module:
 //!
 //! ```rust,ignore
 //! use digraphx_rs::logging::init_logger_with_filter;
 //!
-//! init_logger_with_filter("debug");
+//! init_logger_with_filter("info,digraphx_rs::neg_cycle=trace");
 //! log::debug!("Debug message");
 //! ```
 //!
+//! ## Algorithm records
+//!
+//! With the `std` feature the searches emit records under their module
+//! paths: `digraphx_rs::neg_cycle` logs the outcome of every search, named
+//! after its entry point (`howard`, `howard_pred`, `find_neg_cycle_succ`,
+//! ...), at `debug` and every relaxation pass with its number of distance
+//! updates at `trace`; `digraphx_rs::parametric`
+//! logs each ratio improvement and the final ratio at `debug`.  Without the
+//! feature these records are compiled out.
+//!
+//! ## Capturing records in tests
+//!
+//! [`capture_logs`] collects the records emitted on the current thread
+//! while a closure runs, whatever the configured filter, so that tests can
+//! assert on them:
+//!
+//! ```rust
+//! use digraphx_rs::logging::capture_logs;
+//! use digraphx_rs::NegCycleFinder;
+//!
+//! let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(0, -2)]];
+//! let (cycle, records) = capture_logs(|| NegCycleFinder::new(&graph).howard(&mut vec![0; 2], |w| *w))
+//!     .expect("another logger is installed");
+//! assert!(cycle.is_some());
+//! assert!(records
+//!     .iter()
+//!     .any(|r| r.target == "digraphx_rs::neg_cycle" && r.message.contains("negative cycle")));
+//! ```
 //!
 //! ## Environment Variables
 //!
-//! - `RUST_LOG`: Controls log level (debug, info, warn, error)
+//! - `RUST_LOG`: Controls log level (debug, info, warn, error) and
+//!   per-module filters, unless a filter is passed explicitly
 //! - `RUST_LOG_STYLE`: Controls colored output
 //!
 //! Example:
@@ -32,48 +62,138 @@
 //! RUST_LOG=debug cargo run --features std
 //! ```
 
-#[cfg(feature = "std")]
-use log::LevelFilter;
+use std::cell::RefCell;
+use std::sync::{Mutex, OnceLock};
+
+use env_logger::{Builder, Env};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+/// The logger this module installs.  It forwards every record to the
+/// `env_logger` configured by the `init` functions, if any, and to the
+/// capture in progress on the current thread.
+struct Dispatch {
+    env: OnceLock<env_logger::Logger>,
+}
+
+static DISPATCH: Dispatch = Dispatch {
+    env: OnceLock::new(),
+};
+
+/// Whether `DISPATCH` is the global logger; `false` if another logger was
+/// installed first.
+static INSTALLED: OnceLock<bool> = OnceLock::new();
 
-#[cfg(feature = "std")]
-use std::sync::OnceLock;
+/// Number of [`capture_logs`] calls running on any thread, guarded with
+/// the global maximum level that depends on it.
+static CAPTURES: Mutex<usize> = Mutex::new(0);
 
-#[cfg(feature = "std")]
-static LOGGER_INITIALIZED: OnceLock<()> = OnceLock::new();
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<CapturedRecord>>> = const { RefCell::new(None) };
+}
+
+impl Log for Dispatch {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        CAPTURED.with(|c| c.borrow().is_some())
+            || self.env.get().is_some_and(|env| env.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        CAPTURED.with(|c| {
+            if let Some(records) = c.borrow_mut().as_mut() {
+                records.push(CapturedRecord {
+                    level: record.level(),
+                    target: record.target().to_string(),
+                    message: record.args().to_string(),
+                });
+            }
+        });
+        if let Some(env) = self.env.get() {
+            env.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(env) = self.env.get() {
+            env.flush();
+        }
+    }
+}
+
+/// Install `DISPATCH` as the global logger unless a logger is already set.
+/// Returns `true` if `DISPATCH` is the global logger.
+fn install() -> bool {
+    *INSTALLED.get_or_init(|| log::set_logger(&DISPATCH).is_ok())
+}
+
+/// The error `log` reports when a logger is already set.
+fn already_set() -> SetLoggerError {
+    match log::set_logger(&DISPATCH) {
+        Err(e) => e,
+        Ok(()) => unreachable!("a logger is already set"),
+    }
+}
+
+/// Add `delta` to the number of running captures, then set the global
+/// maximum level to `Trace` while records are being captured, and from the
+/// configured filter otherwise.
+fn update_max_level(delta: isize) {
+    let mut captures = CAPTURES.lock().unwrap_or_else(|e| e.into_inner());
+    *captures = captures.saturating_add_signed(delta);
+    let level = if *captures > 0 {
+        LevelFilter::Trace
+    } else {
+        DISPATCH
+            .env
+            .get()
+            .map_or(LevelFilter::Off, |env| env.filter())
+    };
+    log::set_max_level(level);
+}
+
+/// Install `builder`'s logger behind `DISPATCH`.
+fn try_init(mut builder: Builder) -> Result<(), SetLoggerError> {
+    if !install() || DISPATCH.env.set(builder.build()).is_err() {
+        return Err(already_set());
+    }
+    update_max_level(0);
+    Ok(())
+}
+
+/// Builder reading `RUST_LOG`, with `info` as the default filter.
+fn default_builder() -> Builder {
+    Builder::from_env(Env::default().default_filter_or("info"))
+}
+
+/// Builder with the given filter directives; `RUST_LOG` is ignored.
+fn filter_builder(filter: &str) -> Builder {
+    let mut builder = Builder::from_env(Env::new().write_style("RUST_LOG_STYLE"));
+    builder.parse_filters(filter);
+    builder
+}
 
 /// Initialize the logger with the default filter.
 ///
 /// Reads the log level from the `RUST_LOG` environment variable.
-/// If not set, defaults to `info` level.
-///
-/// # Panics
-///
-/// Panics if the logger has already been initialized.
-#[cfg(feature = "std")]
+/// If not set, defaults to `info` level.  Does nothing if a logger is
+/// already installed; use [`try_init_logger`] to find out.
 pub fn init_logger() {
-    LOGGER_INITIALIZED.get_or_init(|| ());
-    env_logger::Builder::from_default_env()
-        .filter_level(LevelFilter::Info)
-        .init();
+    let _ = try_init_logger();
 }
 
 /// Initialize the logger with a custom filter string.
 ///
-/// The filter string follows `env_logger`'s format:
+/// The filter string follows `env_logger`'s format, a comma-separated list
+/// of directives, each a level or `module=level`:
 /// - `debug` - Debug and above
 /// - `info` - Info and above
 /// - `warn` - Warnings and above
 /// - `error` - Errors only
+/// - `info,digraphx_rs::neg_cycle=trace` - Info and above, and everything
+///   from the negative cycle finders
 ///
-/// # Panics
-///
-/// Panics if the logger has already been initialized.
-#[cfg(feature = "std")]
+/// Does nothing if a logger is already installed.
 pub fn init_logger_with_filter(filter: &str) {
-    LOGGER_INITIALIZED.get_or_init(|| ());
-    env_logger::Builder::from_default_env()
-        .filter_level(filter.parse().unwrap_or(LevelFilter::Info))
-        .init();
+    let _ = try_init_logger_with_filter(filter);
 }
 
 /// Try to initialize the logger without panicking.
@@ -83,36 +203,96 @@ pub fn init_logger_with_filter(filter: &str) {
 ///
 /// # Errors
 ///
-/// Returns an error if the logger has already been initialized.
-#[cfg(feature = "std")]
-pub fn try_init_logger() -> Result<(), log::SetLoggerError> {
-    LOGGER_INITIALIZED.get_or_init(|| ());
-    env_logger::Builder::from_default_env()
-        .filter_level(LevelFilter::Info)
-        .try_init()
+/// Returns an error if a logger, from this module or elsewhere, has already
+/// been installed.
+pub fn try_init_logger() -> Result<(), SetLoggerError> {
+    try_init(default_builder())
 }
 
 /// Try to initialize the logger with a custom filter without panicking.
 ///
+/// See [`init_logger_with_filter`] for the filter syntax; `RUST_LOG` is
+/// not read.
+///
 /// # Errors
 ///
-/// Returns an error if the logger has already been initialized.
-#[cfg(feature = "std")]
-pub fn try_init_logger_with_filter(filter: &str) -> Result<(), log::SetLoggerError> {
-    LOGGER_INITIALIZED.get_or_init(|| ());
-    env_logger::Builder::from_default_env()
-        .filter_level(filter.parse().unwrap_or(LevelFilter::Info))
-        .try_init()
+/// Returns an error if a logger, from this module or elsewhere, has already
+/// been installed.
+pub fn try_init_logger_with_filter(filter: &str) -> Result<(), SetLoggerError> {
+    try_init(filter_builder(filter))
 }
 
 /// Check if the logger has been initialized.
 ///
 /// # Returns
 ///
-/// `true` if the logger is active, `false` otherwise.
-#[cfg(feature = "std")]
+/// `true` if one of the `init` functions of this module installed its
+/// logger, `false` otherwise, in particular when initialization failed
+/// because another logger was installed first.
 pub fn is_logger_initialized() -> bool {
-    LOGGER_INITIALIZED.get().is_some()
+    DISPATCH.env.get().is_some()
+}
+
+/// A log record collected by [`capture_logs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedRecord {
+    /// Level of the record.
+    pub level: Level,
+    /// Target of the record, the emitting module path by default.
+    pub target: String,
+    /// Formatted message.
+    pub message: String,
+}
+
+/// Restores the enclosing capture of the thread and the global maximum
+/// level, also on panic.
+struct CaptureGuard {
+    outer: Option<Option<Vec<CapturedRecord>>>,
+}
+
+impl CaptureGuard {
+    fn finish(mut self) -> Vec<CapturedRecord> {
+        let outer = self.outer.take().flatten();
+        CAPTURED
+            .with(|c| std::mem::replace(&mut *c.borrow_mut(), outer))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for CaptureGuard {
+    fn drop(&mut self) {
+        if let Some(outer) = self.outer.take() {
+            CAPTURED.with(|c| *c.borrow_mut() = outer);
+        }
+        update_max_level(-1);
+    }
+}
+
+/// Run `f` and return its result with the records it emitted.
+///
+/// Every record logged on the current thread while `f` runs is collected,
+/// at any level and regardless of the filter of the installed logger (the
+/// records are still passed on to it).  Records from other threads are not
+/// collected, so tests running in parallel do not see each other's
+/// records.  With nested captures, records go to the innermost one.
+///
+/// The global maximum level is raised to `Trace` while a capture runs on
+/// any thread, and set back from the configured filter when the last one
+/// ends.
+///
+/// # Errors
+///
+/// Returns an error if a logger not from this module is installed.
+pub fn capture_logs<R>(f: impl FnOnce() -> R) -> Result<(R, Vec<CapturedRecord>), SetLoggerError> {
+    if !install() {
+        return Err(already_set());
+    }
+    update_max_level(1);
+    let guard = CaptureGuard {
+        outer: Some(CAPTURED.with(|c| c.borrow_mut().replace(Vec::new()))),
+    };
+    let result = f();
+    Ok((result, guard.finish()))
 }
 
 #[cfg(test)]
@@ -129,10 +309,79 @@ mod tests {
         let _ = try_init_logger_with_filter("debug");
     }
 
+    #[test]
+    fn test_init_logger_twice() {
+        init_logger();
+        init_logger_with_filter("debug");
+        init_logger();
+    }
+
     #[test]
     fn test_is_logger_initialized() {
         let _ = try_init_logger();
         let initialized = is_logger_initialized();
         assert!(initialized);
+        // Only the first initialization succeeds
+        assert!(try_init_logger().is_err());
+        assert!(is_logger_initialized());
+    }
+
+    #[test]
+    fn test_module_filter() {
+        let logger = filter_builder("warn,digraphx_rs::neg_cycle=trace").build();
+        assert_eq!(logger.filter(), LevelFilter::Trace);
+        let record = |target: &'static str, level| {
+            let metadata = Metadata::builder().target(target).level(level).build();
+            logger.enabled(&metadata)
+        };
+        assert!(record("digraphx_rs::neg_cycle", Level::Trace));
+        assert!(!record("digraphx_rs::parametric", Level::Debug));
+        assert!(record("digraphx_rs::parametric", Level::Warn));
+    }
+
+    #[test]
+    fn test_capture_logs() {
+        let (value, records) = capture_logs(|| {
+            log::trace!(target: "outer", "before");
+            let (_, inner) = capture_logs(|| log::debug!(target: "inner", "x = {}", 1)).unwrap();
+            assert_eq!(inner.len(), 1);
+            assert_eq!(inner[0].message, "x = 1");
+            std::thread::spawn(|| log::info!(target: "other", "elsewhere"))
+                .join()
+                .unwrap();
+            log::warn!(target: "outer", "after");
+            7
+        })
+        .unwrap();
+        assert_eq!(value, 7);
+        let seen: Vec<(Level, &str, &str)> = records
+            .iter()
+            .map(|r| (r.level, &*r.target, &*r.message))
+            .collect();
+        assert_eq!(
+            seen,
+            [
+                (Level::Trace, "outer", "before"),
+                (Level::Warn, "outer", "after")
+            ]
+        );
+
+        // Nothing is collected outside a capture
+        log::error!(target: "outer", "ignored");
+        assert!(capture_logs(|| ()).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn test_capture_logs_restores_on_panic() {
+        let caught = std::panic::catch_unwind(|| {
+            capture_logs(|| -> () {
+                log::info!("lost");
+                panic!("boom");
+            })
+        });
+        assert!(caught.is_err());
+        let (_, records) = capture_logs(|| log::info!("kept")).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].message, "kept");
     }
 }
@@ -0,0 +1,98 @@
+//! Crate-internal logging and tracing macros.
+//!
+//! `debug!` and `trace!` forward to the `log` crate when the `std` feature
+//! is enabled.  Without it they expand to a dead branch that still
+//! type-checks the arguments, so no code is emitted and no variable used
+//! only for logging is reported as unused.
+//!
+//! `span!` creates a `tracing` span when the `tracing` feature is enabled
+//! and an inert [`NoSpan`] otherwise; the span fields are then not
+//! evaluated.  `record_debug!` and [`as_debug`] let values bounded only by
+//! [`LogDebug`](crate::LogDebug) appear in spans and records.
+
+macro_rules! debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "std")]
+        log::debug!($($arg)+);
+        #[cfg(not(feature = "std"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "std")]
+        log::trace!($($arg)+);
+        #[cfg(not(feature = "std"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+/// Record a [`LogDebug`](crate::LogDebug) value in the field of a span
+/// created by `span!`, in its `Debug` format.  Without the `tracing` feature
+/// nothing is recorded.
+macro_rules! record_debug {
+    ($span:expr, $field:literal, $value:expr) => {{
+        #[cfg(feature = "tracing")]
+        $span.record(
+            $field,
+            tracing::field::debug($crate::macros::as_debug($value)),
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = (&$span, $value);
+    }};
+}
+
+/// View a [`LogDebug`](crate::LogDebug) value as `Debug`, for `debug!`,
+/// `trace!` and `record_debug!`.
+#[cfg(any(feature = "std", feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn as_debug<T: crate::LogDebug>(value: &T) -> &dyn core::fmt::Debug {
+    value
+}
+
+/// Placeholder for a [`LogDebug`](crate::LogDebug) value: without the `std`
+/// and `tracing` features the records using it are compiled out.
+#[cfg(not(any(feature = "std", feature = "tracing")))]
+#[inline(always)]
+pub(crate) fn as_debug<T: crate::LogDebug>(_: &T) -> &'static dyn core::fmt::Debug {
+    &"_"
+}
+
+/// `tracing::span!` with the `tracing` feature, or a [`NoSpan`] without it.
+/// The level is given by name, e.g. `span!(DEBUG, "howard", nodes = n)`.
+#[cfg(feature = "tracing")]
+macro_rules! span {
+    ($lvl:ident, $($arg:tt)+) => {
+        tracing::span!(tracing::Level::$lvl, $($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! span {
+    ($lvl:ident, $($arg:tt)+) => {
+        $crate::macros::NoSpan
+    };
+}
+
+/// Stand-in for `tracing::Span` when the `tracing` feature is disabled.
+#[cfg(not(feature = "tracing"))]
+#[derive(Clone, Copy)]
+pub(crate) struct NoSpan;
+
+#[cfg(not(feature = "tracing"))]
+impl NoSpan {
+    #[inline(always)]
+    pub(crate) fn entered(self) -> Self {
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) fn record<V>(&self, _field: &str, _value: V) -> &Self {
+        self
+    }
+}
@@ -17,7 +17,10 @@ fn main() {
     let mut dist: HashMap<&str, i32> = [("a", 0), ("b", 0), ("c", 0)].into();
 
     match ncf.howard(&mut dist, |w| *w) {
-        Some(cycle) => println!("Found negative cycle with edges: {:?}", cycle),
+        Some(cycle) => println!(
+            "Found negative cycle {:?} with total weight {}",
+            cycle.nodes, cycle.weight
+        ),
         None => println!("No negative cycle found"),
     }
 }
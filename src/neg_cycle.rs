@@ -1,17 +1,346 @@
-use std::collections::HashMap;
-use std::hash::Hash;
+use std::collections::VecDeque;
+use std::convert::Infallible;
 use std::ops::Add;
 use std::ops::Sub;
 
+use num::{CheckedAdd, CheckedSub};
+
+use crate::compare::{Compare, Exact};
+use crate::config::{Outcome, SolverConfig};
+use crate::error::{check_dist, check_limit, Error};
+use crate::node_map::NodeMap;
+use crate::observer::{NoObserver, Observer};
+use crate::Cycle;
 use crate::Graph;
 use crate::Zero;
 
+/// Result of [`NegCycleFinder::howard_with_config`].
+pub type HowardOutcome<N, E, D> = Outcome<Option<Cycle<N, E, D>>>;
+
+/// Result of [`NegCycleFinder::try_howard`] and
+/// [`NegCycleFinder::howard_checked`].
+pub type TryResult<N, E, D> = Result<Option<Cycle<N, E, D>>, Error<N>>;
+
+/// Result of [`NegCycleFinder::try_howard_with_config`].
+pub type TryHowardOutcome<N, E, D> = Result<HowardOutcome<N, E, D>, Error<N>>;
+
+/// Result of [`NegCycleFinderQ::try_howard_pred`] and
+/// [`NegCycleFinderQ::try_howard_succ`].
+pub type TryEdgesResult<N, E> = Result<Option<Vec<E>>, Error<N>>;
+
+/// Result of [`NegCycleFinderQ::try_howard_pred_with_config`] and
+/// [`NegCycleFinderQ::try_howard_succ_with_config`].
+pub type TryEdgesOutcome<N, E> = Result<Outcome<Option<Vec<E>>>, Error<N>>;
+
+/// Result of [`NegCycleFinderQ::try_find_neg_cycle_pred`] and
+/// [`NegCycleFinderQ::try_find_neg_cycle_succ`].
+pub type TryPairsResult<N> = Result<Option<Vec<(N, N)>>, Error<N>>;
+
+// ---------------------------------------------------------------------------
+// Relaxation core shared by every search
+// ---------------------------------------------------------------------------
+
+/// Distance through an arc, for one relaxation direction and arithmetic.
+trait Step<D> {
+    /// `true` for successor relaxation, which raises $d\[u\]$ to
+    /// $d\[v\] - w(u,v)$; predecessor relaxation lowers $d\[v\]$ to
+    /// $d\[u\] + w(u,v)$.
+    const SUCC: bool;
+
+    /// [`Error`] for the checked steps, [`Infallible`] otherwise.
+    type Error<N>;
+
+    /// Return `d + w` (predecessor) or `d - w` (successor) for the arc
+    /// `tail -> head`.
+    fn step<N>(d: D, w: D, tail: N, head: N) -> Result<D, Self::Error<N>>;
+}
+
+/// Handle of the cycle found by a search, or the overflow that stopped it.
+type SearchResult<N, S, D> = Result<Outcome<Option<N>>, <S as Step<D>>::Error<N>>;
+
+/// A rebuilt cycle, or the node where its chain broke.
+type CycleResult<N, E, D> = Result<Cycle<N, E, D>, Error<N>>;
+
+/// A rebuilt cycle as node pairs, or the node where its chain broke.
+type PairsResult<N> = Result<Vec<(N, N)>, Error<N>>;
+
+/// Every rebuilt cycle of a map, or the node where a chain broke.
+type CyclesResult<N, E, D> = Result<Vec<Cycle<N, E, D>>, Error<N>>;
+
+/// Predecessor relaxation.
+struct Pred;
+
+/// Successor relaxation.
+struct Succ;
+
+/// Predecessor relaxation with `checked_add`.
+struct PredChecked;
+
+/// Successor relaxation with `checked_sub`.
+struct SuccChecked;
+
+impl<D: Add<Output = D>> Step<D> for Pred {
+    const SUCC: bool = false;
+    type Error<N> = Infallible;
+
+    #[inline]
+    fn step<N>(d: D, w: D, _: N, _: N) -> Result<D, Infallible> {
+        Ok(d + w)
+    }
+}
+
+impl<D: Sub<Output = D>> Step<D> for Succ {
+    const SUCC: bool = true;
+    type Error<N> = Infallible;
+
+    #[inline]
+    fn step<N>(d: D, w: D, _: N, _: N) -> Result<D, Infallible> {
+        Ok(d - w)
+    }
+}
+
+impl<D: CheckedAdd> Step<D> for PredChecked {
+    const SUCC: bool = false;
+    type Error<N> = Error<N>;
+
+    #[inline]
+    fn step<N>(d: D, w: D, tail: N, head: N) -> Result<D, Error<N>> {
+        d.checked_add(&w).ok_or(Error::Overflow { tail, head })
+    }
+}
+
+impl<D: CheckedSub> Step<D> for SuccChecked {
+    const SUCC: bool = true;
+    type Error<N> = Error<N>;
+
+    #[inline]
+    fn step<N>(d: D, w: D, tail: N, head: N) -> Result<D, Error<N>> {
+        d.checked_sub(&w).ok_or(Error::Overflow { tail, head })
+    }
+}
+
+/// Rebuild the cycle through the handle found by a search with `f`.
+fn rebuild<N, T, E>(
+    outcome: Outcome<Option<N>>,
+    f: impl FnOnce(N) -> Result<T, E>,
+) -> Result<Outcome<Option<T>>, E> {
+    match outcome {
+        Outcome::Done(handle) => Ok(Outcome::Done(handle.map(f).transpose()?)),
+        Outcome::Interrupted { reason, partial } => Ok(Outcome::Interrupted {
+            reason,
+            partial: partial.map(f).transpose()?,
+        }),
+    }
+}
+
+/// Unwrap the result of an unchecked search.
+#[inline]
+fn infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
+/// Graph, comparison policy and observer shared by the searches of
+/// [`NegCycleFinder`] and [`NegCycleFinderQ`].
+struct Search<'a, G: Graph, C, O> {
+    graph: &'a G,
+    cmp: C,
+    obs: O,
+}
+
+impl<'a, G: Graph, C, O: Observer<G::Node>> Search<'a, G, C, O> {
+    fn with_compare<C2>(self, cmp: C2) -> Search<'a, G, C2, O> {
+        Search {
+            graph: self.graph,
+            cmp,
+            obs: self.obs,
+        }
+    }
+
+    fn with_observer<O2>(self, obs: O2) -> Search<'a, G, C, O2> {
+        Search {
+            graph: self.graph,
+            cmp: self.cmp,
+            obs,
+        }
+    }
+
+    /// One relaxation pass over every arc in the direction of `S`, recording
+    /// the tree arcs in `point_to`.  An update of `node` from `old` to `new`
+    /// is made only if `allow(node, old, new)`.  Returns the number of
+    /// distance updates.
+    fn relax<S, D, M, F, U>(
+        &mut self,
+        point_to: &mut G::Map<(G::Node, G::Edge)>,
+        dist: &mut M,
+        get_weight: &F,
+        allow: &U,
+    ) -> Result<usize, S::Error<G::Node>>
+    where
+        S: Step<D>,
+        D: PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(G::Node, &D, &D) -> bool,
+        C: Compare<D>,
+    {
+        self.obs.pass_start();
+        let mut updates = 0;
+        let mut arcs = 0;
+        for u in self.graph.nodes() {
+            // `du` must follow the updates below, or a later arc could set
+            // `succ[u]` without raising `d[u]`
+            let mut du = dist.get(u).copied().unwrap_or_else(D::zero);
+            for (v, e) in self.graph.neighbors(u) {
+                arcs += 1;
+                let dv = dist.get(v).copied().unwrap_or_else(D::zero);
+                if S::SUCC {
+                    let distance = S::step(dv, get_weight(&e), u, v)?;
+                    if self.cmp.less(&du, &distance) && allow(u, &du, &distance) {
+                        dist.set(u, distance);
+                        point_to.set(u, (v, e));
+                        self.obs.distance_updated(u);
+                        du = distance;
+                        updates += 1;
+                    }
+                } else {
+                    let distance = S::step(du, get_weight(&e), u, v)?;
+                    if self.cmp.less(&distance, &dv) && allow(v, &dv, &distance) {
+                        dist.set(v, distance);
+                        point_to.set(v, (u, e));
+                        self.obs.distance_updated(v);
+                        updates += 1;
+                    }
+                }
+            }
+        }
+        self.obs.pass_end(arcs);
+        Ok(updates)
+    }
+
+    /// [`relax`](Self::relax) as pass number `pass` of the search `name`,
+    /// inside a `relax` span and followed by a trace record.
+    #[allow(clippy::too_many_arguments)]
+    fn pass<S, D, M, F, U>(
+        &mut self,
+        name: &'static str,
+        pass: usize,
+        point_to: &mut G::Map<(G::Node, G::Edge)>,
+        dist: &mut M,
+        get_weight: &F,
+        allow: &U,
+    ) -> Result<usize, S::Error<G::Node>>
+    where
+        S: Step<D>,
+        D: PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(G::Node, &D, &D) -> bool,
+        C: Compare<D>,
+    {
+        let span = span!(TRACE, "relax", pass = pass, updates = tracing::field::Empty).entered();
+        let updates = self.relax::<S, D, M, F, U>(point_to, dist, get_weight, allow)?;
+        span.record("updates", updates);
+        trace!("{}: pass {}, {} updates", name, pass, updates);
+        Ok(updates)
+    }
+
+    /// Return one handle per cycle of `point_to`.
+    fn find_cycles(&mut self, point_to: &G::Map<(G::Node, G::Edge)>) -> Vec<G::Node> {
+        self.obs.find_cycle();
+        find_cycles(self.graph, point_to)
+    }
+
+    /// Return the handle of the first cycle of `point_to`.
+    fn first_cycle(&mut self, point_to: &G::Map<(G::Node, G::Edge)>) -> Option<G::Node> {
+        let handle = self.find_cycles(point_to).first().copied();
+        if let Some(vtx) = handle {
+            self.obs.cycle_found(vtx);
+        }
+        handle
+    }
+
+    /// Howard's algorithm: relax in the direction of `S` until no distance
+    /// changes or `point_to` closes a cycle, whose handle is returned.
+    ///
+    /// `config` is checked before every pass; the passes done are added to
+    /// `passes` so that a caller running several searches can share one
+    /// pass budget.
+    #[allow(clippy::too_many_arguments)]
+    fn run<S, D, M, F, U>(
+        &mut self,
+        name: &'static str,
+        point_to: &mut G::Map<(G::Node, G::Edge)>,
+        dist: &mut M,
+        get_weight: &F,
+        allow: &U,
+        config: &SolverConfig,
+        passes: &mut usize,
+    ) -> SearchResult<G::Node, S, D>
+    where
+        S: Step<D>,
+        D: PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(G::Node, &D, &D) -> bool,
+        C: Compare<D>,
+    {
+        let span = span!(
+            DEBUG,
+            "howard",
+            search = name,
+            nodes = self.graph.num_nodes(),
+            passes = tracing::field::Empty
+        )
+        .entered();
+        let first = *passes;
+        loop {
+            if let Some(reason) = config.check(*passes) {
+                debug!("{}: {} after {} passes", name, reason, *passes - first);
+                return Ok(Outcome::Interrupted {
+                    reason,
+                    partial: None,
+                });
+            }
+            *passes += 1;
+            let pass = *passes - first;
+            span.record("passes", pass);
+            let updates =
+                self.pass::<S, D, M, F, U>(name, pass, point_to, dist, get_weight, allow)?;
+            if updates == 0 {
+                debug!("{}: converged after {} passes", name, pass);
+                return Ok(Outcome::Done(None));
+            }
+            if let Some(vtx) = self.first_cycle(point_to) {
+                debug!(
+                    "{}: negative cycle of {} arcs in pass {}",
+                    name,
+                    cycle_len(point_to, vtx),
+                    pass
+                );
+                return Ok(Outcome::Done(Some(vtx)));
+            }
+        }
+    }
+}
+
 /// Negative cycle finder using Howard's policy iteration method.
 ///
 /// Generic over any graph type `G` that implements the [`Graph`] trait.
 /// The graph is treated as a container of containers: the outer container
 /// maps each node to its neighbors, the inner maps each neighbor to
-/// an edge weight.
+/// an edge payload.
+///
+/// The edge payload `G::Edge` and the distance domain `D` are independent:
+/// every search takes a `get_weight: Fn(&G::Edge) -> D` closure that maps
+/// the payload into the domain in which distances are accumulated.
+///
+/// Whether an arc improves a distance is decided by the comparison policy
+/// `C`, [`Exact`] unless set with [`with_compare`](Self::with_compare).
+/// Progress is reported to the [`Observer`] `O`, [`NoObserver`] unless set
+/// with [`with_observer`](Self::with_observer).
 ///
 /// # Example
 ///
@@ -29,126 +358,757 @@ use crate::Zero;
 /// let result = ncf.howard(&mut dist, |w| *w);
 /// assert!(result.is_none()); // no negative cycle
 /// ```
-pub struct NegCycleFinder<'a, G: Graph> {
-    graph: &'a G,
-    pred: HashMap<G::Node, (G::Node, G::Weight)>,
+///
+/// Structured edge payloads are mapped into the distance domain:
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use digraphx_rs::{graph_from_edges, NegCycleFinder};
+///
+/// #[derive(Clone, Copy)]
+/// struct Arc {
+///     cost: i32,
+///     time: i32,
+/// }
+///
+/// let graph = graph_from_edges(&[
+///     (0, 1, Arc { cost: 1, time: 2 }),
+///     (1, 0, Arc { cost: -4, time: 1 }),
+/// ]);
+/// let mut ncf = NegCycleFinder::new(&graph);
+/// let mut dist: HashMap<i32, f64> = HashMap::new();
+/// let result = ncf.howard(&mut dist, |e| f64::from(e.cost) + 0.5 * f64::from(e.time));
+/// assert!(result.is_some());
+/// ```
+pub struct NegCycleFinder<'a, G: Graph, C = Exact, O = NoObserver> {
+    search: Search<'a, G, C, O>,
+    pred: G::Map<(G::Node, G::Edge)>,
 }
 
-impl<'a, G: Graph> NegCycleFinder<'a, G>
-where
-    G::Weight: Add<Output = G::Weight> + PartialOrd + Copy + Zero,
-    G::Node: Copy + Eq + Hash,
-{
+impl<'a, G: Graph> NegCycleFinder<'a, G> {
+    /// Create a new finder for the given graph.
+    pub fn new(graph: &'a G) -> Self {
+        NegCycleFinder {
+            search: Search {
+                graph,
+                cmp: Exact,
+                obs: NoObserver,
+            },
+            pred: graph.node_map(),
+        }
+    }
+}
+
+impl<'a, G: Graph, C, O: Observer<G::Node>> NegCycleFinder<'a, G, C, O> {
     /// Return a reference to the underlying graph.
     pub fn graph(&self) -> &'a G {
-        self.graph
+        self.search.graph
     }
 
-    /// Create a new finder for the given graph.
-    pub fn new(graph: &'a G) -> Self {
+    /// Replace the comparison policy, e.g. with a
+    /// [`Tolerance`](crate::compare::Tolerance) for floating-point weights.
+    pub fn with_compare<C2>(self, cmp: C2) -> NegCycleFinder<'a, G, C2, O> {
+        NegCycleFinder {
+            search: self.search.with_compare(cmp),
+            pred: self.pred,
+        }
+    }
+
+    /// Return the comparison policy.
+    pub fn compare(&self) -> &C {
+        &self.search.cmp
+    }
+
+    /// Replace the observer, e.g. with a [`Stats`](crate::observer::Stats)
+    /// or a `&mut Stats` to count the search events.
+    pub fn with_observer<O2>(self, obs: O2) -> NegCycleFinder<'a, G, C, O2> {
         NegCycleFinder {
-            graph,
-            pred: HashMap::new(),
+            search: self.search.with_observer(obs),
+            pred: self.pred,
         }
     }
 
+    /// Return the observer.
+    pub fn observer(&self) -> &O {
+        &self.search.obs
+    }
+
+    /// Return the observer mutably.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.search.obs
+    }
+
     /// Perform one Bellman–Ford relaxation pass.
     ///
     /// For each edge $(u, v)$ in the graph, checks the triangle inequality:
     ///
     /// $$ d\[v\] > d\[u\] + w(u,v) $$
     ///
-    /// and updates the predecessor map if so. Returns `true` if any distance was changed.
-    pub fn relax<F>(&mut self, dist: &mut HashMap<G::Node, G::Weight>, get_weight: &F) -> bool
+    /// and updates the predecessor map if so, with the comparison made by the
+    /// finder's [`Compare`] policy. Returns `true` if any distance was changed.
+    pub fn relax<D, M, F>(&mut self, dist: &mut M, get_weight: &F) -> bool
     where
-        F: Fn(&G::Weight) -> G::Weight,
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
     {
-        let mut changed = false;
-        for u in self.graph.nodes() {
-            let du = *dist.get(&u).unwrap_or(&G::Weight::zero());
-            for (v, w) in self.graph.neighbors(u) {
-                let distance = du + get_weight(&w);
-                let dv = *dist.get(&v).unwrap_or(&G::Weight::zero());
-                if dv > distance {
-                    dist.insert(v, distance);
-                    self.pred.insert(v, (u, w));
-                    changed = true;
+        let allow = |_: G::Node, _: &D, _: &D| true;
+        infallible(
+            self.search
+                .relax::<Pred, _, _, _, _>(&mut self.pred, dist, get_weight, &allow),
+        ) > 0
+    }
+
+    /// Reconstruct the cycle through `handle` from the predecessor map.
+    ///
+    /// The nodes and arcs are returned in traversal order, and the total
+    /// weight is accumulated with `get_weight`.
+    fn cycle_list<D, F>(&self, handle: G::Node, get_weight: &F) -> CycleResult<G::Node, G::Edge, D>
+    where
+        D: Add<Output = D> + Copy + Zero,
+        F: Fn(&G::Edge) -> D,
+    {
+        Cycle::try_from_pred(handle, &self.pred, get_weight)
+    }
+
+    /// Howard's algorithm: find a negative cycle in the graph.
+    ///
+    /// Returns `Some(cycle)` where `cycle` holds the nodes, the arcs and the
+    /// total weight of a negative cycle, or `None` if no negative cycle
+    /// exists.
+    ///
+    /// # Type parameters
+    ///
+    /// * `D` — distance domain in which `dist` is kept.
+    /// * `F` — weight-extraction closure mapping an edge payload into `D`
+    ///   (typically `\|w\| *w` when the payload is the weight itself, or a
+    ///   projection for structured edge types).
+    pub fn howard<D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+    ) -> Option<Cycle<G::Node, G::Edge, D>>
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        self.howard_with_config(dist, get_weight, &SolverConfig::new())
+            .into_inner()
+    }
+
+    /// Howard's algorithm under the limits of `config`.
+    ///
+    /// Like [`howard`](Self::howard), but checks `config` before every
+    /// relaxation pass.  When a limit is hit the search stops with
+    /// [`Outcome::Interrupted`] holding `None`; the partial state is left in
+    /// `dist`, whose potentials can seed a later call.
+    ///
+    /// ```rust
+    /// use digraphx_rs::config::{Interrupt, SolverConfig};
+    /// use digraphx_rs::NegCycleFinder;
+    ///
+    /// // A path whose distances settle one node per pass
+    /// let graph: Vec<Vec<(usize, i32)>> = vec![vec![], vec![(0, -1)], vec![(1, -1)], vec![(2, -1)]];
+    /// let mut ncf = NegCycleFinder::new(&graph);
+    /// let mut dist = vec![0; 4];
+    /// let config = SolverConfig::new().with_max_passes(1);
+    /// let outcome = ncf.howard_with_config(&mut dist, |w| *w, &config);
+    /// assert_eq!(outcome.interrupt(), Some(Interrupt::IterationLimit));
+    /// assert_eq!(dist, [-1, -1, -1, 0]);
+    ///
+    /// let outcome = ncf.howard_with_config(&mut dist, |w| *w, &SolverConfig::new());
+    /// assert_eq!(outcome.done(), Some(None));
+    /// assert_eq!(dist, [-3, -2, -1, 0]);
+    /// ```
+    pub fn howard_with_config<D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        config: &SolverConfig,
+    ) -> HowardOutcome<G::Node, G::Edge, D>
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        self.howard_counted(dist, &get_weight, config, &mut 0)
+            .unwrap_or(Outcome::Done(None))
+    }
+
+    /// Body of [`howard_with_config`](Self::howard_with_config), adding the
+    /// relaxation passes done to `passes` so that a caller running several
+    /// searches can share one pass budget.  Fails with
+    /// [`Error::InconsistentGraph`] if the cycle found cannot be rebuilt.
+    pub(crate) fn howard_counted<D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: &F,
+        config: &SolverConfig,
+        passes: &mut usize,
+    ) -> TryHowardOutcome<G::Node, G::Edge, D>
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        let outcome = infallible(self.run::<Pred, D, M, F>(dist, get_weight, config, passes));
+        rebuild(outcome, |vtx| self.cycle_list(vtx, get_weight))
+    }
+
+    /// Return every cycle of the predecessor map left by the last search.
+    pub(crate) fn pred_cycles<D, F>(&self, get_weight: &F) -> CyclesResult<G::Node, G::Edge, D>
+    where
+        D: Add<Output = D> + Copy + Zero,
+        F: Fn(&G::Edge) -> D,
+    {
+        find_cycles(self.search.graph, &self.pred)
+            .into_iter()
+            .map(|vtx| self.cycle_list(vtx, get_weight))
+            .collect()
+    }
+
+    /// Reset the predecessor map and run the shared search loop.
+    fn run<S, D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: &F,
+        config: &SolverConfig,
+        passes: &mut usize,
+    ) -> SearchResult<G::Node, S, D>
+    where
+        S: Step<D>,
+        D: PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        self.pred = self.search.graph.node_map();
+        let allow = |_: G::Node, _: &D, _: &D| true;
+        self.search.run::<S, D, M, F, _>(
+            "howard",
+            &mut self.pred,
+            dist,
+            get_weight,
+            &allow,
+            config,
+            passes,
+        )
+    }
+
+    /// Howard's algorithm without panics.
+    ///
+    /// Like [`howard`](Self::howard), but `dist` must have an entry for every
+    /// node, including the nodes that only appear as neighbors; a missing
+    /// entry is reported as [`Error::MissingNode`] before the search starts
+    /// (where `howard` would treat it as zero, or panic on a short `Vec`).
+    /// Distances are computed as in [`howard_checked`](Self::howard_checked),
+    /// so an arithmetic overflow is reported as [`Error::Overflow`], and a
+    /// broken predecessor chain as [`Error::InconsistentGraph`].
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use digraphx_rs::{graph_from_edges, Error, NegCycleFinder};
+    ///
+    /// let graph = graph_from_edges(&[("a", "b", 1), ("b", "a", -2)]);
+    /// let mut ncf = NegCycleFinder::new(&graph);
+    /// let mut dist: HashMap<&str, i32> = [("a", 0)].into();
+    /// assert_eq!(ncf.try_howard(&mut dist, |w| *w), Err(Error::MissingNode("b")));
+    /// dist.insert("b", 0);
+    /// assert!(ncf.try_howard(&mut dist, |w| *w).unwrap().is_some());
+    /// ```
+    pub fn try_howard<D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+    ) -> TryResult<G::Node, G::Edge, D>
+    where
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        check_dist(self.search.graph, dist)?;
+        self.howard_checked(dist, get_weight)
+    }
+
+    /// Howard's algorithm with overflow-checked arithmetic.
+    ///
+    /// Same as [`howard`](Self::howard), but every distance and the cycle
+    /// weight are computed with `checked_add`.  If a sum leaves the range
+    /// of `D`, the search stops with an [`Error::Overflow`] naming the arc
+    /// being added instead of wrapping (release builds) or panicking (debug
+    /// builds).  A broken predecessor chain is reported as
+    /// [`Error::InconsistentGraph`].
+    ///
+    /// ```rust
+    /// use digraphx_rs::{Error, NegCycleFinder};
+    ///
+    /// let big = -1_500_000_000i32;
+    /// let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, big)], vec![(2, big)], vec![(0, 0)]];
+    /// let mut ncf = NegCycleFinder::new(&graph);
+    /// let mut dist = vec![0; 3];
+    /// let err = ncf.howard_checked(&mut dist, |w| *w).unwrap_err();
+    /// assert_eq!(err, Error::Overflow { tail: 1, head: 2 });
+    /// ```
+    pub fn howard_checked<D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+    ) -> TryResult<G::Node, G::Edge, D>
+    where
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        self.checked_with_config(dist, &get_weight, &SolverConfig::new())
+            .map(Outcome::into_inner)
+    }
+
+    /// Howard's algorithm without panics, under the limits of `config`.
+    ///
+    /// Combines the checks of [`try_howard`](Self::try_howard) with the
+    /// limits of [`howard_with_config`](Self::howard_with_config).  Running
+    /// out of passes fails with [`Error::IterationLimit`]; a deadline or a
+    /// cancellation still yields [`Outcome::Interrupted`].
+    ///
+    /// ```rust
+    /// use digraphx_rs::config::SolverConfig;
+    /// use digraphx_rs::{Error, NegCycleFinder};
+    ///
+    /// let graph: Vec<Vec<(usize, i32)>> = vec![vec![], vec![(0, -1)], vec![(1, -1)], vec![(2, -1)]];
+    /// let mut ncf = NegCycleFinder::new(&graph);
+    /// let config = SolverConfig::new().with_max_passes(1);
+    /// let res = ncf.try_howard_with_config(&mut vec![0; 4], |w| *w, &config);
+    /// assert_eq!(res, Err(Error::IterationLimit(1)));
+    /// ```
+    pub fn try_howard_with_config<D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        config: &SolverConfig,
+    ) -> TryHowardOutcome<G::Node, G::Edge, D>
+    where
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        check_dist(self.search.graph, dist)?;
+        check_limit(self.checked_with_config(dist, &get_weight, config)?, config)
+    }
+
+    /// Body of [`howard_checked`](Self::howard_checked) under the limits of
+    /// `config`.
+    fn checked_with_config<D, M, F>(
+        &mut self,
+        dist: &mut M,
+        get_weight: &F,
+        config: &SolverConfig,
+    ) -> TryHowardOutcome<G::Node, G::Edge, D>
+    where
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        let outcome = self.run::<PredChecked, D, M, F>(dist, get_weight, config, &mut 0)?;
+        rebuild(outcome, |vtx| {
+            Cycle::from_pred_checked(vtx, &self.pred, get_weight)
+        })
+    }
+
+    /// Howard's algorithm as an iterator over every negative cycle found.
+    ///
+    /// Unlike [`howard`](Self::howard), which stops at the first cycle, the
+    /// iterator keeps relaxing and yields each newly discovered cycle once.
+    /// The nodes of a reported cycle are frozen: their distances and tree
+    /// arcs are no longer updated, so the cycle cannot drive the distances
+    /// down without bound, and the cycles yielded are node-disjoint.  It
+    /// ends when the relaxation converges, or after `num_nodes` consecutive
+    /// passes that reveal no new cycle.  The caller may stop earlier by
+    /// simply dropping the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use digraphx_rs::{graph_from_edges, NegCycleFinder};
+    ///
+    /// // two disjoint negative cycles
+    /// let graph = graph_from_edges(&[(0, 1, 1), (1, 0, -2), (2, 3, 1), (3, 2, -2)]);
+    /// let mut ncf = NegCycleFinder::new(&graph);
+    /// let mut dist: HashMap<i32, i32> = HashMap::new();
+    /// let cycles: Vec<_> = ncf.howard_iter(&mut dist, |w| *w).collect();
+    /// assert_eq!(cycles.len(), 2);
+    /// ```
+    pub fn howard_iter<'f, D, M, F>(
+        &'f mut self,
+        dist: &'f mut M,
+        get_weight: F,
+    ) -> NegCycles<'f, 'a, G, D, M, F, C, O>
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        self.pred = self.search.graph.node_map();
+        NegCycles {
+            frozen: self.search.graph.node_map(),
+            finder: self,
+            dist,
+            get_weight,
+            pending: VecDeque::new(),
+            reported: 0,
+            passes: 0,
+            idle: 0,
+            done: false,
+        }
+    }
+}
+
+/// Iterator returned by [`NegCycleFinder::howard_iter`].
+pub struct NegCycles<'f, 'a, G: Graph, D, M, F, C = Exact, O = NoObserver> {
+    finder: &'f mut NegCycleFinder<'a, G, C, O>,
+    dist: &'f mut M,
+    get_weight: F,
+    pending: VecDeque<Cycle<G::Node, G::Edge, D>>,
+    frozen: G::Map<()>,
+    reported: usize,
+    passes: usize,
+    idle: usize,
+    done: bool,
+}
+
+impl<G, D, M, F, C, O> Iterator for NegCycles<'_, '_, G, D, M, F, C, O>
+where
+    G: Graph,
+    D: Add<Output = D> + PartialOrd + Copy + Zero,
+    M: NodeMap<G::Node, D>,
+    F: Fn(&G::Edge) -> D,
+    C: Compare<D>,
+    O: Observer<G::Node>,
+{
+    type Item = Cycle<G::Node, G::Edge, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cycle) = self.pending.pop_front() {
+                return Some(cycle);
+            }
+            if self.done {
+                return None;
+            }
+            self.passes += 1;
+            let finder = &mut *self.finder;
+            let frozen = &self.frozen;
+            let allow = |node: G::Node, _: &D, _: &D| !frozen.contains_node(node);
+            let updates = infallible(finder.search.pass::<Pred, _, _, _, _>(
+                "howard_iter",
+                self.passes,
+                &mut finder.pred,
+                self.dist,
+                &self.get_weight,
+                &allow,
+            ));
+            if updates == 0 {
+                self.done = true;
+                return None;
+            }
+            let mut found = false;
+            for vtx in finder.search.find_cycles(&finder.pred) {
+                // A tree arc of a frozen node never changes, so a cycle
+                // through one was reported in an earlier pass
+                if self.frozen.contains_node(vtx) {
+                    continue;
+                }
+                let Ok(cycle) = finder.cycle_list(vtx, &self.get_weight) else {
+                    continue;
+                };
+                finder.search.obs.cycle_found(vtx);
+                for &node in &cycle.nodes {
+                    self.frozen.set(node, ());
+                }
+                self.reported += 1;
+                self.pending.push_back(cycle);
+                found = true;
+            }
+            if found {
+                self.idle = 0;
+            } else if self.reported > 0 {
+                self.idle += 1;
+                if self.idle >= finder.search.graph.num_nodes().max(1) {
+                    self.done = true;
                 }
             }
         }
-        changed
     }
+}
 
-    /// Find a cycle in the predecessor graph.
-    ///
-    /// Returns the start node of a cycle if one exists.
-    fn find_cycle(&self) -> Option<G::Node> {
-        let mut visited: HashMap<G::Node, G::Node> = HashMap::new();
-        for vtx in self.graph.nodes() {
-            if visited.contains_key(&vtx) {
-                continue;
+/// Iterator returned by [`NegCycleFinderQ::howard_pred_iter`] and
+/// [`NegCycleFinderQ::howard_succ_iter`].
+pub struct NegCyclesQ<'f, 'a, G: Graph, D, M, F, U, C = Exact, O = NoObserver> {
+    finder: &'f mut NegCycleFinderQ<'a, G, C, O>,
+    dist: &'f mut M,
+    get_weight: F,
+    update_ok: U,
+    use_succ: bool,
+    pending: VecDeque<Cycle<G::Node, G::Edge, D>>,
+    frozen: G::Map<()>,
+    reported: usize,
+    passes: usize,
+    idle: usize,
+    done: bool,
+    error: Option<Error<G::Node>>,
+}
+
+impl<'f, 'a, G: Graph, D, M, F, U, C, O> NegCyclesQ<'f, 'a, G, D, M, F, U, C, O> {
+    fn new(
+        finder: &'f mut NegCycleFinderQ<'a, G, C, O>,
+        dist: &'f mut M,
+        get_weight: F,
+        update_ok: U,
+        use_succ: bool,
+    ) -> Self {
+        NegCyclesQ {
+            frozen: finder.search.graph.node_map(),
+            finder,
+            dist,
+            get_weight,
+            update_ok,
+            use_succ,
+            pending: VecDeque::new(),
+            reported: 0,
+            passes: 0,
+            idle: 0,
+            done: false,
+            error: None,
+        }
+    }
+
+    /// Return the comparison policy of the finder.
+    pub fn compare(&self) -> &C {
+        &self.finder.search.cmp
+    }
+
+    /// Return the first cycle that could not be rebuilt and was skipped,
+    /// as an [`Error::InconsistentGraph`].
+    pub(crate) fn error(&self) -> Option<Error<G::Node>> {
+        self.error
+    }
+}
+
+impl<G, D, M, F, U, C, O> Iterator for NegCyclesQ<'_, '_, G, D, M, F, U, C, O>
+where
+    G: Graph,
+    D: Add<Output = D> + Sub<Output = D> + PartialOrd + Copy + Zero,
+    M: NodeMap<G::Node, D>,
+    F: Fn(&G::Edge) -> D,
+    U: Fn(&D, &D) -> bool,
+    C: Compare<D>,
+    O: Observer<G::Node>,
+{
+    type Item = Cycle<G::Node, G::Edge, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cycle) = self.pending.pop_front() {
+                return Some(cycle);
             }
-            let mut utx = vtx;
-            while !visited.contains_key(&utx) {
-                visited.insert(utx, vtx);
-                match self.pred.get(&utx) {
-                    None => break,
-                    Some(&(prev, _)) => {
-                        utx = prev;
-                        if let Some(&root) = visited.get(&utx) {
-                            if root == vtx {
-                                return Some(utx);
-                            }
-                            break;
-                        }
+            if self.done {
+                return None;
+            }
+            self.passes += 1;
+            let finder = &mut *self.finder;
+            let update_ok = &self.update_ok;
+            let frozen = &self.frozen;
+            let allow = |node: G::Node, old: &D, new: &D| {
+                !frozen.contains_node(node) && update_ok(old, new)
+            };
+            let (name, point_to) = if self.use_succ {
+                ("howard_succ_iter", &mut finder.succ)
+            } else {
+                ("howard_pred_iter", &mut finder.pred)
+            };
+            let updates = if self.use_succ {
+                finder.search.pass::<Succ, _, _, _, _>(
+                    name,
+                    self.passes,
+                    point_to,
+                    self.dist,
+                    &self.get_weight,
+                    &allow,
+                )
+            } else {
+                finder.search.pass::<Pred, _, _, _, _>(
+                    name,
+                    self.passes,
+                    point_to,
+                    self.dist,
+                    &self.get_weight,
+                    &allow,
+                )
+            };
+            if infallible(updates) == 0 {
+                self.done = true;
+                return None;
+            }
+            let point_to = &*point_to;
+            let mut found = false;
+            for vtx in finder.search.find_cycles(point_to) {
+                if self.frozen.contains_node(vtx) {
+                    continue;
+                }
+                let graph = finder.search.graph;
+                let cycle = match cycle_of(graph, vtx, point_to, &self.get_weight, self.use_succ) {
+                    Ok(cycle) => cycle,
+                    Err(e) => {
+                        self.error.get_or_insert(e);
+                        continue;
                     }
+                };
+                finder.search.obs.cycle_found(vtx);
+                for &node in &cycle.nodes {
+                    self.frozen.set(node, ());
+                }
+                self.reported += 1;
+                self.pending.push_back(cycle);
+                found = true;
+            }
+            if found {
+                self.idle = 0;
+            } else if self.reported > 0 {
+                self.idle += 1;
+                if self.idle >= finder.search.graph.num_nodes().max(1) {
+                    self.done = true;
                 }
             }
         }
-        None
     }
+}
 
-    /// Reconstruct the cycle edges starting from `handle`.
-    fn cycle_list(&self, handle: G::Node) -> Vec<G::Weight> {
-        let mut vtx = handle;
-        let mut cycle = Vec::new();
-        loop {
-            let &(u, w) = self.pred.get(&vtx).unwrap();
-            cycle.push(w);
-            vtx = u;
-            if vtx == handle {
-                break;
+// ---------------------------------------------------------------------------
+// Shared helpers
+// ---------------------------------------------------------------------------
+
+/// Find every cycle in a `point_to` forest (`pred` or `succ`).
+///
+/// Each node points to at most one other node, so every weakly connected
+/// component contains at most one cycle.  Returns one handle per cycle.
+pub(crate) fn find_cycles<G: Graph>(
+    graph: &G,
+    point_to: &G::Map<(G::Node, G::Edge)>,
+) -> Vec<G::Node> {
+    let mut visited: G::Map<G::Node> = graph.node_map();
+    let mut handles = Vec::new();
+    for vtx in graph.nodes() {
+        if visited.contains_node(vtx) {
+            continue;
+        }
+        let mut utx = vtx;
+        while !visited.contains_node(utx) {
+            visited.set(utx, vtx);
+            match point_to.get(utx) {
+                None => break,
+                Some(&(next, _)) => {
+                    utx = next;
+                    if let Some(&root) = visited.get(utx) {
+                        if root == vtx {
+                            handles.push(utx);
+                        }
+                        break;
+                    }
+                }
             }
         }
-        cycle
     }
+    handles
+}
 
-    /// Howard's algorithm: find a negative cycle in the graph.
-    ///
-    /// Returns `Some(cycle)` where `cycle` is a list of edge weights
-    /// forming a negative cycle, or `None` if no negative cycle exists.
-    ///
-    /// # Type parameters
-    ///
-    /// * `F` — weight-extraction closure (typically `\|w\| *w` when the
-    ///   weight is the edge data itself, or a projection for structured
-    ///   edge types).
-    pub fn howard<F>(
-        &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
-        get_weight: F,
-    ) -> Option<Vec<G::Weight>>
-    where
-        F: Fn(&G::Weight) -> G::Weight,
-    {
-        self.pred.clear();
-        while self.relax(dist, &get_weight) {
-            if let Some(vtx) = self.find_cycle() {
-                return Some(self.cycle_list(vtx));
-            }
+/// Return the number of arcs on the cycle of `point_to` through `handle`.
+fn cycle_len<N: Copy + Eq, E, P: NodeMap<N, (N, E)>>(point_to: &P, handle: N) -> usize {
+    let mut len = 0;
+    let mut vtx = handle;
+    while let Some(&(next, _)) = point_to.get(vtx) {
+        len += 1;
+        vtx = next;
+        if vtx == handle {
+            break;
+        }
+    }
+    len
+}
+
+/// Walk the cycle through `handle` in the given mapping, collecting
+/// `f(node, next, edge)` for every arc.  Fails with
+/// [`Error::InconsistentGraph`] if the walk breaks off, or runs into a cycle
+/// that does not pass through `handle`.
+fn walk_cycle<G: Graph, T>(
+    graph: &G,
+    handle: G::Node,
+    point_to: &G::Map<(G::Node, G::Edge)>,
+    f: impl Fn(G::Node, G::Node, G::Edge) -> T,
+) -> Result<Vec<T>, Error<G::Node>> {
+    let mut seen: G::Map<()> = graph.node_map();
+    let mut vtx = handle;
+    let mut cycle = Vec::new();
+    loop {
+        if seen.contains_node(vtx) {
+            return Err(Error::InconsistentGraph(handle));
+        }
+        seen.set(vtx, ());
+        let &(next, e) = point_to.get(vtx).ok_or(Error::InconsistentGraph(vtx))?;
+        cycle.push(f(vtx, next, e));
+        vtx = next;
+        if vtx == handle {
+            break;
         }
-        None
     }
+    Ok(cycle)
+}
+
+/// Build a [`Cycle`] through `handle` from the given mapping.
+///
+/// `pred` maps a node to the tail of its incoming tree arc and `succ`
+/// maps a node to the head of its outgoing tree arc, so only the `pred`
+/// walk has to be reversed to obtain traversal order.
+fn cycle_of<G, D, F>(
+    graph: &G,
+    handle: G::Node,
+    point_to: &G::Map<(G::Node, G::Edge)>,
+    get_weight: &F,
+    forward: bool,
+) -> CycleResult<G::Node, G::Edge, D>
+where
+    G: Graph,
+    D: Add<Output = D> + Copy + Zero,
+    F: Fn(&G::Edge) -> D,
+{
+    let mut edges = walk_cycle(graph, handle, point_to, |vtx, next, e| {
+        if forward {
+            (vtx, next, e)
+        } else {
+            (next, vtx, e)
+        }
+    })?;
+    if !forward {
+        edges.reverse();
+    }
+    let weight = edges
+        .iter()
+        .fold(D::zero(), |acc, (_, _, e)| acc + get_weight(e));
+    let nodes = edges.iter().map(|&(u, _, _)| u).collect();
+    Ok(Cycle {
+        nodes,
+        edges,
+        weight,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -162,7 +1122,10 @@ where
 /// - **Successor-based** Howard (`howard_succ`) — reverse relaxation
 /// - An **`update_ok`** callback that gates distance updates
 ///
-/// Generic over any graph type `G` that implements [`Graph`].
+/// Generic over any graph type `G` that implements [`Graph`].  As with
+/// [`NegCycleFinder`], distances live in a domain `D` chosen by the
+/// `get_weight` closure and are compared with the policy `C`; progress is
+/// reported to the [`Observer`] `O`.
 ///
 /// # Example
 ///
@@ -178,51 +1141,65 @@ where
 /// let result = ncfq.howard_pred(&mut dist, |w| *w, |_, _| true);
 /// assert!(result.is_some());
 /// ```
-pub struct NegCycleFinderQ<'a, G: Graph> {
-    graph: &'a G,
-    pred: HashMap<G::Node, (G::Node, G::Weight)>,
-    succ: HashMap<G::Node, (G::Node, G::Weight)>,
+pub struct NegCycleFinderQ<'a, G: Graph, C = Exact, O = NoObserver> {
+    search: Search<'a, G, C, O>,
+    pred: G::Map<(G::Node, G::Edge)>,
+    succ: G::Map<(G::Node, G::Edge)>,
 }
 
-impl<'a, G: Graph> NegCycleFinderQ<'a, G>
-where
-    G::Weight: Add<Output = G::Weight> + Sub<Output = G::Weight> + PartialOrd + Copy + Zero,
-    G::Node: Copy + Eq + Hash,
-{
+impl<'a, G: Graph> NegCycleFinderQ<'a, G> {
     /// Create a new constrained finder for the given graph.
     pub fn new(graph: &'a G) -> Self {
         NegCycleFinderQ {
-            graph,
-            pred: HashMap::new(),
-            succ: HashMap::new(),
+            search: Search {
+                graph,
+                cmp: Exact,
+                obs: NoObserver,
+            },
+            pred: graph.node_map(),
+            succ: graph.node_map(),
         }
     }
+}
 
-    /// Find a cycle in the given mapping (`pred` or `succ`).
-    fn find_cycle(&self, point_to: &HashMap<G::Node, (G::Node, G::Weight)>) -> Option<G::Node> {
-        let mut visited: HashMap<G::Node, G::Node> = HashMap::new();
-        for vtx in self.graph.nodes() {
-            if visited.contains_key(&vtx) {
-                continue;
-            }
-            let mut utx = vtx;
-            while !visited.contains_key(&utx) {
-                visited.insert(utx, vtx);
-                match point_to.get(&utx) {
-                    None => break,
-                    Some(&(prev, _)) => {
-                        utx = prev;
-                        if let Some(&root) = visited.get(&utx) {
-                            if root == vtx {
-                                return Some(utx);
-                            }
-                            break;
-                        }
-                    }
-                }
-            }
+impl<'a, G: Graph, C, O: Observer<G::Node>> NegCycleFinderQ<'a, G, C, O> {
+    /// Return a reference to the underlying graph.
+    pub fn graph(&self) -> &'a G {
+        self.search.graph
+    }
+
+    /// Replace the comparison policy, e.g. with a
+    /// [`Tolerance`](crate::compare::Tolerance) for floating-point weights.
+    pub fn with_compare<C2>(self, cmp: C2) -> NegCycleFinderQ<'a, G, C2, O> {
+        NegCycleFinderQ {
+            search: self.search.with_compare(cmp),
+            pred: self.pred,
+            succ: self.succ,
         }
-        None
+    }
+
+    /// Return the comparison policy.
+    pub fn compare(&self) -> &C {
+        &self.search.cmp
+    }
+
+    /// Replace the observer, as in [`NegCycleFinder::with_observer`].
+    pub fn with_observer<O2>(self, obs: O2) -> NegCycleFinderQ<'a, G, C, O2> {
+        NegCycleFinderQ {
+            search: self.search.with_observer(obs),
+            pred: self.pred,
+            succ: self.succ,
+        }
+    }
+
+    /// Return the observer.
+    pub fn observer(&self) -> &O {
+        &self.search.obs
+    }
+
+    /// Return the observer mutably.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.search.obs
     }
 
     /// Predecessor relaxation (Bellman–Ford style) with constraint.
@@ -232,218 +1209,582 @@ where
     /// $$ d\[v\] > d\[u\] + w(u,v) $$
     ///
     /// AND $\text{update\_ok}(d_{\text{old}}, d_{\text{new}})$ is `true`.
-    pub fn relax_pred<F, U>(
+    pub fn relax_pred<D, M, F, U>(&mut self, dist: &mut M, get_weight: &F, update_ok: &U) -> bool
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        let allow = |_: G::Node, old: &D, new: &D| update_ok(old, new);
+        infallible(
+            self.search
+                .relax::<Pred, _, _, _, _>(&mut self.pred, dist, get_weight, &allow),
+        ) > 0
+    }
+
+    /// Successor relaxation (reverse Bellman–Ford style) with constraint.
+    ///
+    /// For each edge $(u, v)$, updates $d\[u\]$ when:
+    ///
+    /// $$ d\[u\] < d\[v\] - w(u,v) $$
+    ///
+    /// AND $\text{update\_ok}(d_{\text{old}}, d_{\text{new}})$ is `true`.
+    pub fn relax_succ<D, M, F, U>(&mut self, dist: &mut M, get_weight: &F, update_ok: &U) -> bool
+    where
+        D: Sub<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        let allow = |_: G::Node, old: &D, new: &D| update_ok(old, new);
+        infallible(
+            self.search
+                .relax::<Succ, _, _, _, _>(&mut self.succ, dist, get_weight, &allow),
+        ) > 0
+    }
+
+    /// Reset the mapping of the direction of `S` and run the shared search
+    /// loop under the name `name`.
+    fn run<S, D, M, F, U>(
         &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
+        name: &'static str,
+        dist: &mut M,
         get_weight: &F,
         update_ok: &U,
-    ) -> bool
+        config: &SolverConfig,
+    ) -> SearchResult<G::Node, S, D>
     where
-        F: Fn(&G::Weight) -> G::Weight,
-        U: Fn(&G::Weight, &G::Weight) -> bool,
+        S: Step<D>,
+        D: PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
     {
-        let mut changed = false;
-        for u in self.graph.nodes() {
-            let du = *dist.get(&u).unwrap_or(&G::Weight::zero());
-            for (v, w) in self.graph.neighbors(u) {
-                let distance = du + get_weight(&w);
-                let dv = *dist.get(&v).unwrap_or(&G::Weight::zero());
-                if dv > distance && update_ok(&dv, &distance) {
-                    dist.insert(v, distance);
-                    self.pred.insert(v, (u, w));
-                    changed = true;
-                }
-            }
+        let point_to = if S::SUCC {
+            &mut self.succ
+        } else {
+            &mut self.pred
+        };
+        *point_to = self.search.graph.node_map();
+        let allow = |_: G::Node, old: &D, new: &D| update_ok(old, new);
+        self.search
+            .run::<S, D, M, F, _>(name, point_to, dist, get_weight, &allow, config, &mut 0)
+    }
+
+    /// Reconstruct a cycle from the given mapping.
+    fn cycle_list(
+        &self,
+        handle: G::Node,
+        point_to: &G::Map<(G::Node, G::Edge)>,
+    ) -> Result<Vec<G::Edge>, Error<G::Node>> {
+        walk_cycle(self.search.graph, handle, point_to, |_, _, e| e)
+    }
+
+    /// Check whether the cycle starting at `handle` is negative.
+    ///
+    /// A cycle is negative if for any edge $(u,v)$ on the cycle:
+    ///
+    /// $$ d\[v\] > d\[u\] + w(u,v) $$
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` does not lie on a cycle of the predecessor map;
+    /// see [`try_is_negative`](Self::try_is_negative).
+    #[deprecated(note = "panics off the predecessor cycles; use `try_is_negative`")]
+    pub fn is_negative<D, M, F>(&self, handle: G::Node, dist: &M, get_weight: &F) -> bool
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        match self.try_is_negative(handle, dist, get_weight) {
+            Ok(negative) => negative,
+            Err(_) => panic!("handle does not lie on a cycle of the predecessor map"),
         }
-        changed
     }
 
-    /// Successor relaxation (reverse Bellman–Ford style) with constraint.
+    /// Check whether the cycle of the predecessor map through `handle` is
+    /// negative, as [`is_negative`](Self::is_negative) does, but fail with
+    /// [`Error::InconsistentGraph`] instead of panicking when `handle` does
+    /// not lie on such a cycle.
+    pub fn try_is_negative<D, M, F>(
+        &self,
+        handle: G::Node,
+        dist: &M,
+        get_weight: &F,
+    ) -> Result<bool, Error<G::Node>>
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        C: Compare<D>,
+    {
+        let at = |n| dist.get(n).copied().unwrap_or_else(D::zero);
+        let arcs = walk_cycle(self.search.graph, handle, &self.pred, |v, u, e| (u, v, e))?;
+        Ok(arcs
+            .into_iter()
+            .any(|(u, v, e)| self.search.cmp.less(&(at(u) + get_weight(&e)), &at(v))))
+    }
+
+    /// Predecessor-based Howard's algorithm with constraint.
     ///
-    /// For each edge $(u, v)$, updates $d\[u\]$ when:
+    /// Returns the first negative cycle found as edge payloads, or `None`.
+    pub fn howard_pred<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> Option<Vec<G::Edge>>
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        self.howard_pred_with_config(dist, get_weight, update_ok, &SolverConfig::new())
+            .into_inner()
+    }
+
+    /// Predecessor-based Howard's algorithm under the limits of `config`.
     ///
-    /// $$ d\[u\] < d\[v\] - w(u,v) $$
+    /// See [`NegCycleFinder::howard_with_config`]; on interruption the
+    /// partial state is left in `dist`.
+    pub fn howard_pred_with_config<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+        config: &SolverConfig,
+    ) -> Outcome<Option<Vec<G::Edge>>>
+    where
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        let outcome = infallible(self.run::<Pred, D, M, F, U>(
+            "howard_pred",
+            dist,
+            &get_weight,
+            &update_ok,
+            config,
+        ));
+        outcome.map(|handle| handle.and_then(|vtx| self.cycle_list(vtx, &self.pred).ok()))
+    }
+
+    /// Successor-based Howard's algorithm with constraint.
+    ///
+    /// Returns the first negative cycle found as edge payloads, or `None`.
+    pub fn howard_succ<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> Option<Vec<G::Edge>>
+    where
+        D: Sub<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        self.howard_succ_with_config(dist, get_weight, update_ok, &SolverConfig::new())
+            .into_inner()
+    }
+
+    /// Successor-based Howard's algorithm under the limits of `config`.
+    ///
+    /// See [`NegCycleFinder::howard_with_config`]; on interruption the
+    /// partial state is left in `dist`.
+    pub fn howard_succ_with_config<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+        config: &SolverConfig,
+    ) -> Outcome<Option<Vec<G::Edge>>>
+    where
+        D: Sub<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        let outcome = infallible(self.run::<Succ, D, M, F, U>(
+            "howard_succ",
+            dist,
+            &get_weight,
+            &update_ok,
+            config,
+        ));
+        outcome.map(|handle| handle.and_then(|vtx| self.cycle_list(vtx, &self.succ).ok()))
+    }
+
+    /// Predecessor-based Howard's algorithm without panics.
+    ///
+    /// Like [`howard_pred`](Self::howard_pred), with the checks of
+    /// [`NegCycleFinder::try_howard`].
+    pub fn try_howard_pred<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> TryEdgesResult<G::Node, G::Edge>
+    where
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        check_dist(self.search.graph, dist)?;
+        self.howard_pred_checked(dist, get_weight, update_ok)
+    }
+
+    /// Successor-based Howard's algorithm without panics.
+    ///
+    /// Like [`howard_succ`](Self::howard_succ), with the checks of
+    /// [`NegCycleFinder::try_howard`].
+    pub fn try_howard_succ<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> TryEdgesResult<G::Node, G::Edge>
+    where
+        D: CheckedSub + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        check_dist(self.search.graph, dist)?;
+        self.howard_succ_checked(dist, get_weight, update_ok)
+    }
+
+    /// Predecessor-based Howard's algorithm with overflow-checked
+    /// arithmetic.
+    ///
+    /// Same as [`howard_pred`](Self::howard_pred), but fails with
+    /// [`Error::Overflow`] when a distance leaves the range of `D`, and with
+    /// [`Error::InconsistentGraph`] on a broken predecessor chain; see
+    /// [`NegCycleFinder::howard_checked`].
+    pub fn howard_pred_checked<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> TryEdgesResult<G::Node, G::Edge>
+    where
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        self.edges_checked::<PredChecked, D, M, F, U>(
+            "howard_pred",
+            dist,
+            &get_weight,
+            &update_ok,
+            &SolverConfig::new(),
+        )
+        .map(Outcome::into_inner)
+    }
+
+    /// Successor-based Howard's algorithm with overflow-checked
+    /// arithmetic.
+    ///
+    /// Same as [`howard_succ`](Self::howard_succ), but fails with
+    /// [`Error::Overflow`] when a distance leaves the range of `D`, and with
+    /// [`Error::InconsistentGraph`] on a broken successor chain.
+    pub fn howard_succ_checked<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> TryEdgesResult<G::Node, G::Edge>
+    where
+        D: CheckedSub + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        self.edges_checked::<SuccChecked, D, M, F, U>(
+            "howard_succ",
+            dist,
+            &get_weight,
+            &update_ok,
+            &SolverConfig::new(),
+        )
+        .map(Outcome::into_inner)
+    }
+
+    /// Predecessor-based Howard's algorithm without panics, under the
+    /// limits of `config`.
     ///
-    /// AND $\text{update\_ok}(d_{\text{old}}, d_{\text{new}})$ is `true`.
-    pub fn relax_succ<F, U>(
+    /// See [`NegCycleFinder::try_howard_with_config`].
+    pub fn try_howard_pred_with_config<D, M, F, U>(
         &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
-        get_weight: &F,
-        update_ok: &U,
-    ) -> bool
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+        config: &SolverConfig,
+    ) -> TryEdgesOutcome<G::Node, G::Edge>
     where
-        F: Fn(&G::Weight) -> G::Weight,
-        U: Fn(&G::Weight, &G::Weight) -> bool,
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
     {
-        let mut changed = false;
-        for u in self.graph.nodes() {
-            let du = *dist.get(&u).unwrap_or(&G::Weight::zero());
-            for (v, w) in self.graph.neighbors(u) {
-                let distance = *dist.get(&v).unwrap_or(&G::Weight::zero()) - get_weight(&w);
-                if du < distance && update_ok(&du, &distance) {
-                    dist.insert(u, distance);
-                    self.succ.insert(u, (v, w));
-                    changed = true;
-                }
-            }
-        }
-        changed
+        check_dist(self.search.graph, dist)?;
+        let outcome = self.edges_checked::<PredChecked, D, M, F, U>(
+            "howard_pred",
+            dist,
+            &get_weight,
+            &update_ok,
+            config,
+        )?;
+        check_limit(outcome, config)
     }
 
-    /// Reconstruct a cycle from the given mapping.
-    fn cycle_list(
-        &self,
-        handle: G::Node,
-        point_to: &HashMap<G::Node, (G::Node, G::Weight)>,
-    ) -> Vec<G::Weight> {
-        let mut vtx = handle;
-        let mut cycle = Vec::new();
-        loop {
-            let &(u, w) = point_to.get(&vtx).unwrap();
-            cycle.push(w);
-            vtx = u;
-            if vtx == handle {
-                break;
-            }
-        }
-        cycle
+    /// Successor-based Howard's algorithm without panics, under the limits
+    /// of `config`.
+    ///
+    /// See [`NegCycleFinder::try_howard_with_config`].
+    pub fn try_howard_succ_with_config<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+        config: &SolverConfig,
+    ) -> TryEdgesOutcome<G::Node, G::Edge>
+    where
+        D: CheckedSub + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        check_dist(self.search.graph, dist)?;
+        let outcome = self.edges_checked::<SuccChecked, D, M, F, U>(
+            "howard_succ",
+            dist,
+            &get_weight,
+            &update_ok,
+            config,
+        )?;
+        check_limit(outcome, config)
     }
 
-    /// Check whether the cycle starting at `handle` is negative.
-    ///
-    /// A cycle is negative if for any edge $(u,v)$ on the cycle:
-    ///
-    /// $$ d\[v\] > d\[u\] + w(u,v) $$
-    pub fn is_negative<F>(
-        &self,
-        handle: G::Node,
-        dist: &HashMap<G::Node, G::Weight>,
+    /// Run an overflow-checked search and rebuild its cycle from the map
+    /// of the relaxation direction.
+    fn edges_checked<S, D, M, F, U>(
+        &mut self,
+        name: &'static str,
+        dist: &mut M,
         get_weight: &F,
-    ) -> bool
+        update_ok: &U,
+        config: &SolverConfig,
+    ) -> TryEdgesOutcome<G::Node, G::Edge>
     where
-        F: Fn(&G::Weight) -> G::Weight,
+        S: Step<D, Error<G::Node> = Error<G::Node>>,
+        D: PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
     {
-        let mut vtx = handle;
-        loop {
-            let &(u, w) = self.pred.get(&vtx).unwrap();
-            let dv = *dist.get(&vtx).unwrap_or(&G::Weight::zero());
-            let du = *dist.get(&u).unwrap_or(&G::Weight::zero());
-            if dv > du + get_weight(&w) {
-                return true;
-            }
-            vtx = u;
-            if vtx == handle {
-                break;
-            }
-        }
-        false
+        let outcome = self.run::<S, D, M, F, U>(name, dist, get_weight, update_ok, config)?;
+        let point_to = if S::SUCC { &self.succ } else { &self.pred };
+        rebuild(outcome, |vtx| self.cycle_list(vtx, point_to))
     }
 
-    /// Predecessor-based Howard's algorithm with constraint.
+    /// Predecessor-based Howard's algorithm as an iterator over every
+    /// negative cycle found.
     ///
-    /// Returns the first negative cycle found as edge weights, or `None`.
-    pub fn howard_pred<F, U>(
-        &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
+    /// See [`NegCycleFinder::howard_iter`] for the termination rule.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use digraphx_rs::{graph_from_edges, NegCycleFinderQ};
+    ///
+    /// let graph = graph_from_edges(&[(0, 1, 1), (1, 0, -2), (2, 3, 1), (3, 2, -2)]);
+    /// let mut ncfq = NegCycleFinderQ::new(&graph);
+    /// let mut dist: HashMap<i32, i32> = HashMap::new();
+    /// let cycles: Vec<_> = ncfq.howard_pred_iter(&mut dist, |w| *w, |_, _| true).collect();
+    /// assert_eq!(cycles.len(), 2);
+    /// ```
+    pub fn howard_pred_iter<'f, D, M, F, U>(
+        &'f mut self,
+        dist: &'f mut M,
         get_weight: F,
         update_ok: U,
-    ) -> Option<Vec<G::Weight>>
+    ) -> NegCyclesQ<'f, 'a, G, D, M, F, U, C, O>
     where
-        F: Fn(&G::Weight) -> G::Weight,
-        U: Fn(&G::Weight, &G::Weight) -> bool,
+        D: Add<Output = D> + Sub<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
     {
-        self.pred.clear();
-        while self.relax_pred(dist, &get_weight, &update_ok) {
-            if let Some(vtx) = self.find_cycle(&self.pred) {
-                debug_assert!(self.is_negative(vtx, dist, &get_weight));
-                return Some(self.cycle_list(vtx, &self.pred));
-            }
-        }
-        None
+        self.pred = self.search.graph.node_map();
+        NegCyclesQ::new(self, dist, get_weight, update_ok, false)
     }
 
-    /// Successor-based Howard's algorithm with constraint.
+    /// Successor-based Howard's algorithm as an iterator over every
+    /// negative cycle found.
     ///
-    /// Returns the first negative cycle found as edge weights, or `None`.
-    pub fn howard_succ<F, U>(
-        &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
+    /// See [`NegCycleFinder::howard_iter`] for the termination rule.
+    pub fn howard_succ_iter<'f, D, M, F, U>(
+        &'f mut self,
+        dist: &'f mut M,
         get_weight: F,
         update_ok: U,
-    ) -> Option<Vec<G::Weight>>
+    ) -> NegCyclesQ<'f, 'a, G, D, M, F, U, C, O>
     where
-        F: Fn(&G::Weight) -> G::Weight,
-        U: Fn(&G::Weight, &G::Weight) -> bool,
+        D: Add<Output = D> + Sub<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
     {
-        self.succ.clear();
-        while self.relax_succ(dist, &get_weight, &update_ok) {
-            if let Some(vtx) = self.find_cycle(&self.succ) {
-                return Some(self.cycle_list(vtx, &self.succ));
-            }
-        }
-        None
+        self.succ = self.search.graph.node_map();
+        NegCyclesQ::new(self, dist, get_weight, update_ok, true)
     }
 
     /// Reconstruct a cycle as node-pair edges from the given mapping.
     fn cycle_list_node_pairs(
         &self,
         handle: G::Node,
-        point_to: &HashMap<G::Node, (G::Node, G::Weight)>,
-    ) -> Vec<(G::Node, G::Node)> {
-        let mut vtx = handle;
-        let mut cycle = Vec::new();
-        loop {
-            let &(u, _) = point_to.get(&vtx).unwrap();
-            cycle.push((u, vtx));
-            vtx = u;
-            if vtx == handle {
-                break;
-            }
-        }
-        cycle
+        point_to: &G::Map<(G::Node, G::Edge)>,
+    ) -> PairsResult<G::Node> {
+        walk_cycle(self.search.graph, handle, point_to, |vtx, next, _| {
+            (next, vtx)
+        })
     }
 
     /// Find one negative cycle (predecessor) returning node-pair edges.
     ///
     /// Useful for parametric algorithms that need edge endpoints.
-    pub fn find_neg_cycle_pred<F, U>(
+    pub fn find_neg_cycle_pred<D, M, F, U>(
         &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
+        dist: &mut M,
         get_weight: F,
         update_ok: U,
     ) -> Option<Vec<(G::Node, G::Node)>>
     where
-        F: Fn(&G::Weight) -> G::Weight,
-        U: Fn(&G::Weight, &G::Weight) -> bool,
+        D: Add<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
     {
-        self.pred.clear();
-        while self.relax_pred(dist, &get_weight, &update_ok) {
-            if let Some(vtx) = self.find_cycle(&self.pred) {
-                debug_assert!(self.is_negative(vtx, dist, &get_weight));
-                return Some(self.cycle_list_node_pairs(vtx, &self.pred));
-            }
-        }
-        None
+        let outcome = infallible(self.run::<Pred, D, M, F, U>(
+            "find_neg_cycle_pred",
+            dist,
+            &get_weight,
+            &update_ok,
+            &SolverConfig::new(),
+        ));
+        outcome
+            .into_inner()
+            .and_then(|vtx| self.cycle_list_node_pairs(vtx, &self.pred).ok())
     }
 
     /// Find one negative cycle (successor) returning node-pair edges.
-    pub fn find_neg_cycle_succ<F, U>(
+    pub fn find_neg_cycle_succ<D, M, F, U>(
         &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
+        dist: &mut M,
         get_weight: F,
         update_ok: U,
     ) -> Option<Vec<(G::Node, G::Node)>>
     where
-        F: Fn(&G::Weight) -> G::Weight,
-        U: Fn(&G::Weight, &G::Weight) -> bool,
+        D: Sub<Output = D> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
     {
-        self.succ.clear();
-        while self.relax_succ(dist, &get_weight, &update_ok) {
-            if let Some(vtx) = self.find_cycle(&self.succ) {
-                return Some(self.cycle_list_node_pairs(vtx, &self.succ));
-            }
+        let outcome = infallible(self.run::<Succ, D, M, F, U>(
+            "find_neg_cycle_succ",
+            dist,
+            &get_weight,
+            &update_ok,
+            &SolverConfig::new(),
+        ));
+        outcome
+            .into_inner()
+            .and_then(|vtx| self.cycle_list_node_pairs(vtx, &self.succ).ok())
+    }
+
+    /// Like [`find_neg_cycle_pred`](Self::find_neg_cycle_pred), with the
+    /// checks of [`NegCycleFinder::try_howard`].
+    pub fn try_find_neg_cycle_pred<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> TryPairsResult<G::Node>
+    where
+        D: CheckedAdd + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        check_dist(self.search.graph, dist)?;
+        let outcome = self.run::<PredChecked, D, M, F, U>(
+            "find_neg_cycle_pred",
+            dist,
+            &get_weight,
+            &update_ok,
+            &SolverConfig::new(),
+        )?;
+        match outcome.into_inner() {
+            Some(vtx) => self.cycle_list_node_pairs(vtx, &self.pred).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Like [`find_neg_cycle_succ`](Self::find_neg_cycle_succ), with the
+    /// checks of [`NegCycleFinder::try_howard`].
+    pub fn try_find_neg_cycle_succ<D, M, F, U>(
+        &mut self,
+        dist: &mut M,
+        get_weight: F,
+        update_ok: U,
+    ) -> TryPairsResult<G::Node>
+    where
+        D: CheckedSub + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, D>,
+        F: Fn(&G::Edge) -> D,
+        U: Fn(&D, &D) -> bool,
+        C: Compare<D>,
+    {
+        check_dist(self.search.graph, dist)?;
+        let outcome = self.run::<SuccChecked, D, M, F, U>(
+            "find_neg_cycle_succ",
+            dist,
+            &get_weight,
+            &update_ok,
+            &SolverConfig::new(),
+        )?;
+        match outcome.into_inner() {
+            Some(vtx) => self.cycle_list_node_pairs(vtx, &self.succ).map(Some),
+            None => Ok(None),
         }
-        None
     }
 }
 
@@ -478,6 +1819,25 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_negative_cycle_nodes_and_edges() {
+        // 3 -> 0 leads into the cycle but is not part of it
+        let graph = graph_from_edges(&[(3, 0, 1i32), (0, 1, 2), (1, 2, 3), (2, 0, -6)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let cycle = ncf.howard(&mut dist, |w| *w).unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.weight, -1);
+        let mut nodes = cycle.nodes.clone();
+        nodes.sort();
+        assert_eq!(nodes, vec![0, 1, 2]);
+        for (i, &(u, v, w)) in cycle.edges.iter().enumerate() {
+            assert_eq!(u, cycle.nodes[i]);
+            assert_eq!(v, cycle.nodes[(i + 1) % cycle.len()]);
+            assert_eq!(graph[&u][&v], w);
+        }
+    }
+
     #[test]
     fn test_empty_graph() {
         let graph: HashMap<i32, HashMap<i32, i32>> = HashMap::new();
@@ -528,6 +1888,194 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_howard_checked_matches_howard() {
+        let graphs = [
+            graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3)]),
+            graph_from_edges(&[(0, 1, 7), (0, 2, 5), (1, 0, 0), (1, 2, 3), (2, 1, 1)]),
+            graph_from_edges(&[(0, 1, -2), (1, 0, 1), (1, 2, -9), (2, 1, 10)]),
+        ];
+        for graph in &graphs {
+            let mut dist: HashMap<i32, i32> = HashMap::new();
+            let expected = NegCycleFinder::new(graph).howard(&mut dist, |w| *w);
+            let mut dist: HashMap<i32, i32> = HashMap::new();
+            let checked = NegCycleFinder::new(graph).howard_checked(&mut dist, |w| *w);
+            assert_eq!(checked, Ok(expected));
+        }
+    }
+
+    #[test]
+    fn test_howard_checked_overflow() {
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, i32::MIN)], vec![(0, -1)]];
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = vec![0; 2];
+        let err = ncf.howard_checked(&mut dist, |w| *w).unwrap_err();
+        assert_eq!(err, Error::Overflow { tail: 1, head: 0 });
+        assert!(err.to_string().contains("overflow"));
+
+        // Wide enough domain: the same graph has an ordinary negative cycle
+        let mut dist = vec![0i64; 2];
+        let cycle = ncf.howard_checked(&mut dist, |w| i64::from(*w));
+        assert_eq!(cycle.unwrap().unwrap().weight, i64::from(i32::MIN) - 1);
+    }
+
+    #[test]
+    fn test_vec_dist() {
+        let graph: Vec<Vec<(usize, i32)>> =
+            vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3), (3, 0)], vec![]];
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = vec![0; 4];
+        let cycle = ncf.howard(&mut dist, |w| *w).unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.weight, -1);
+        assert!(dist[0] < 0 || dist[1] < 0 || dist[2] < 0);
+    }
+
+    #[test]
+    fn test_map_adapter_dist() {
+        use crate::map_adapter::MapAdapter;
+        let graph = MapAdapter::new(vec![vec![(1usize, 1i32)], vec![(0, -2)], vec![]]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = MapAdapter::new(vec![0; 3]);
+        let cycles: Vec<_> = ncf.howard_iter(&mut dist, |w| *w).collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].weight, -1);
+    }
+
+    #[derive(Clone, Copy)]
+    struct Arc {
+        cost: i32,
+        time: i32,
+        id: usize,
+    }
+
+    #[test]
+    fn test_struct_edge_payload() {
+        let graph = graph_from_edges(&[
+            (
+                0,
+                1,
+                Arc {
+                    cost: 2,
+                    time: 1,
+                    id: 0,
+                },
+            ),
+            (
+                1,
+                2,
+                Arc {
+                    cost: 2,
+                    time: 1,
+                    id: 1,
+                },
+            ),
+            (
+                2,
+                0,
+                Arc {
+                    cost: 2,
+                    time: 1,
+                    id: 2,
+                },
+            ),
+        ]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        // cost - 3 * time is negative on every edge
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        let result = ncf.howard(&mut dist, |e| f64::from(e.cost) - 3.0 * f64::from(e.time));
+        let cycle = result.unwrap();
+        let mut ids: Vec<usize> = cycle.payloads().map(|e| e.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![0, 1, 2]);
+
+        // cost - time is positive on every edge
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        let result = ncf.howard(&mut dist, |e| f64::from(e.cost) - f64::from(e.time));
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_howard_iter_disjoint_cycles() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 0, -2), (2, 3, 1), (3, 2, -3)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let mut cycles: Vec<_> = ncf.howard_iter(&mut dist, |w| *w).collect();
+        cycles.sort_by_key(|c| c.weight);
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].weight, -2);
+        assert_eq!(cycles[1].weight, -1);
+    }
+
+    #[test]
+    fn test_howard_iter_distinct() {
+        // every pair of nodes forms a negative 2-cycle, and so do both triangles
+        let graph = graph_from_edges(&[
+            (0, 1, -1i32),
+            (1, 0, -1),
+            (1, 2, -1),
+            (2, 1, -1),
+            (2, 0, -1),
+            (0, 2, -1),
+        ]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let cycles: Vec<_> = ncf.howard_iter(&mut dist, |w| *w).collect();
+        assert!(!cycles.is_empty());
+        for (i, a) in cycles.iter().enumerate() {
+            assert!(a.weight < 0);
+            for b in &cycles[i + 1..] {
+                assert!(a.nodes.iter().all(|n| !b.nodes.contains(n)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_howard_iter_stops_relaxing_reported_cycles() {
+        // Relaxing the cycle once more per pass would overflow `i32` long
+        // before `num_nodes` idle passes have gone by
+        let big = -1_000_000_000;
+        let mut graph: Vec<Vec<(usize, i32)>> = vec![vec![]; 16];
+        graph[0].push((1, big));
+        graph[1].push((0, 0));
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = vec![0; 16];
+        let cycles: Vec<_> = ncf.howard_iter(&mut dist, |w| *w).collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].weight, big);
+        assert_eq!(&dist[..2], &[big, big]);
+
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        for succ in [false, true] {
+            let mut dist = vec![0; 16];
+            let count = if succ {
+                ncfq.howard_succ_iter(&mut dist, |w| *w, |_, _| true)
+                    .count()
+            } else {
+                ncfq.howard_pred_iter(&mut dist, |w| *w, |_, _| true)
+                    .count()
+            };
+            assert_eq!(count, 1);
+        }
+    }
+
+    #[test]
+    fn test_howard_iter_early_stop() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 0, -2), (2, 3, 1), (3, 2, -2)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let first = ncf.howard_iter(&mut dist, |w| *w).next();
+        assert!(first.is_some());
+    }
+
+    #[test]
+    fn test_howard_iter_no_cycle() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, 1)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        assert_eq!(ncf.howard_iter(&mut dist, |w| *w).count(), 0);
+    }
+
     // --- NegCycleFinderQ tests ---
 
     #[test]
@@ -564,6 +2112,340 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_q_succ_no_zero_cycle() {
+        // Raising d[0] through 0 -> 1 must not make the zero-weight
+        // self-loop look like an improving arc
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, -5), (0, 0)], vec![]];
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist = vec![0, 0];
+        assert!(ncfq.howard_succ(&mut dist, |w| *w, |_, _| true).is_none());
+        assert_eq!(dist, [5, 0]);
+    }
+
+    #[test]
+    fn test_q_relax_succ_keeps_best_arc() {
+        // 0 -> 2 improves on the old d[0] but not on the one set through
+        // 0 -> 1, so it must neither lower d[0] nor take over succ[0]
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, -5), (2, -1)], vec![], vec![]];
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist = vec![0, 0, 0];
+        assert!(ncfq.relax_succ(&mut dist, &|w| *w, &|_, _| true));
+        assert_eq!(dist, [5, 0, 0]);
+        assert_eq!(ncfq.succ.get(0), Some(&(1, -5)));
+    }
+
+    #[test]
+    fn test_tolerance_nearly_zero_cycle() {
+        use crate::compare::Tolerance;
+
+        // 0.3 - 0.1 - 0.2 rounds to a tiny negative number
+        let graph = graph_from_edges(&[(0, 1, 0.3), (1, 2, -0.1), (2, 0, -0.2)]);
+        let tol = Tolerance::new(1e-12, 1e-12);
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        let spurious = NegCycleFinder::new(&graph).howard(&mut dist, |w| *w);
+        assert!(spurious.unwrap().weight > -1e-15);
+
+        let mut ncf = NegCycleFinder::new(&graph).with_compare(tol);
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        assert!(ncf.howard(&mut dist, |w| *w).is_none());
+        assert_eq!(ncf.howard_iter(&mut dist, |w| *w).count(), 0);
+
+        let mut ncfq = NegCycleFinderQ::new(&graph).with_compare(tol);
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        assert!(ncfq.howard_pred(&mut dist, |w| *w, |_, _| true).is_none());
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        assert!(ncfq.howard_succ(&mut dist, |w| *w, |_, _| true).is_none());
+
+        // A cycle that is negative beyond the tolerance is still found
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        let cycle = ncf.howard(&mut dist, |w| *w - 1e-6).unwrap();
+        assert!(cycle.weight < -1e-6);
+        let mut dist: HashMap<i32, f64> = HashMap::new();
+        assert!(ncfq
+            .howard_succ(&mut dist, |w| *w - 1e-6, |_, _| true)
+            .is_some());
+    }
+
+    #[test]
+    fn test_q_checked() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3)]);
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let cycle = ncfq.howard_pred_checked(&mut dist, |w| *w, |_, _| true);
+        assert_eq!(cycle.unwrap().unwrap().iter().sum::<i32>(), -1);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let cycle = ncfq.howard_succ_checked(&mut dist, |w| *w, |_, _| true);
+        assert_eq!(cycle.unwrap().unwrap().iter().sum::<i32>(), -1);
+
+        let big = -1_500_000_000i32;
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, big)], vec![(2, big)], vec![]];
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist = vec![0; 3];
+        let err = ncfq.howard_pred_checked(&mut dist, |w| *w, |_, _| true);
+        assert_eq!(err, Err(Error::Overflow { tail: 1, head: 2 }));
+        let mut dist = vec![0; 3];
+        let err = ncfq.howard_succ_checked(&mut dist, |w| *w, |_, _| true);
+        assert_eq!(err, Err(Error::Overflow { tail: 0, head: 1 }));
+    }
+
+    #[test]
+    fn test_howard_with_config() {
+        use crate::config::Interrupt;
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let cycle = ncf.howard_with_config(&mut dist, |w| *w, &SolverConfig::new());
+        assert_eq!(cycle.done().unwrap().unwrap().weight, -1);
+
+        let config = SolverConfig::new().with_max_passes(0);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let outcome = ncf.howard_with_config(&mut dist, |w| *w, &config);
+        assert_eq!(outcome.interrupt(), Some(Interrupt::IterationLimit));
+        assert!(dist.is_empty());
+
+        let config = SolverConfig::new().with_cancel(Arc::new(AtomicBool::new(true)));
+        let outcome = ncf.howard_with_config(&mut dist, |w| *w, &config);
+        assert_eq!(outcome.interrupt(), Some(Interrupt::Cancelled));
+    }
+
+    #[test]
+    fn test_howard_pass_limit_keeps_partial_dist() {
+        // Nodes are scanned in index order, so this path of 20 negative arcs
+        // pointing down the indices settles one node per pass
+        let n = 20;
+        let graph: Vec<Vec<(usize, i32)>> = (0..=n)
+            .map(|u| if u > 0 { vec![(u - 1, -1)] } else { vec![] })
+            .collect();
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = vec![0; n + 1];
+        let config = SolverConfig::new().with_max_passes(5);
+        let outcome = ncf.howard_with_config(&mut dist, |w| *w, &config);
+        assert!(outcome.is_interrupted());
+        assert_eq!(dist[0], -5);
+
+        // Resuming from the partial potentials converges to the same result
+        assert!(ncf.howard(&mut dist, |w| *w).is_none());
+        let mut fresh = vec![0; n + 1];
+        assert!(ncf.howard(&mut fresh, |w| *w).is_none());
+        assert_eq!(dist, fresh);
+    }
+
+    #[test]
+    fn test_q_with_config() {
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)]];
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let ok = |_: &i32, _: &i32| true;
+        let unlimited = SolverConfig::new();
+        let limited = SolverConfig::new().with_max_passes(0);
+
+        let res = ncfq.howard_pred_with_config(&mut vec![0; 3], |w| *w, ok, &unlimited);
+        assert_eq!(res.done().unwrap().unwrap().iter().sum::<i32>(), -1);
+        let res = ncfq.howard_succ_with_config(&mut vec![0; 3], |w| *w, ok, &unlimited);
+        assert_eq!(res.done().unwrap().unwrap().iter().sum::<i32>(), -1);
+        let res = ncfq.howard_pred_with_config(&mut vec![0; 3], |w| *w, ok, &limited);
+        assert!(res.is_interrupted());
+        let res = ncfq.howard_succ_with_config(&mut vec![0; 3], |w| *w, ok, &limited);
+        assert!(res.is_interrupted());
+    }
+
+    #[test]
+    fn test_try_with_config() {
+        use crate::config::Interrupt;
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)]];
+        let unlimited = SolverConfig::new();
+        let limited = SolverConfig::new().with_max_passes(0);
+        let cancelled = SolverConfig::new().with_cancel(Arc::new(AtomicBool::new(true)));
+
+        let mut ncf = NegCycleFinder::new(&graph);
+        let res = ncf.try_howard_with_config(&mut vec![0; 3], |w| *w, &unlimited);
+        assert_eq!(res.unwrap().done().unwrap().unwrap().weight, -1);
+        let res = ncf.try_howard_with_config(&mut vec![0; 3], |w| *w, &limited);
+        assert_eq!(res, Err(Error::IterationLimit(0)));
+        let res = ncf.try_howard_with_config(&mut vec![0; 3], |w| *w, &cancelled);
+        assert_eq!(res.unwrap().interrupt(), Some(Interrupt::Cancelled));
+        let res = ncf.try_howard_with_config(&mut vec![0; 2], |w| *w, &unlimited);
+        assert_eq!(res, Err(Error::MissingNode(2)));
+
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let ok = |_: &i32, _: &i32| true;
+        let res = ncfq.try_howard_pred_with_config(&mut vec![0; 3], |w| *w, ok, &unlimited);
+        assert_eq!(
+            res.unwrap().done().unwrap().unwrap().iter().sum::<i32>(),
+            -1
+        );
+        let res = ncfq.try_howard_succ_with_config(&mut vec![0; 3], |w| *w, ok, &unlimited);
+        assert_eq!(
+            res.unwrap().done().unwrap().unwrap().iter().sum::<i32>(),
+            -1
+        );
+        let res = ncfq.try_howard_pred_with_config(&mut vec![0; 3], |w| *w, ok, &limited);
+        assert_eq!(res, Err(Error::IterationLimit(0)));
+        let res = ncfq.try_howard_succ_with_config(&mut vec![0; 3], |w| *w, ok, &limited);
+        assert_eq!(res, Err(Error::IterationLimit(0)));
+        let res = ncfq.try_howard_succ_with_config(&mut vec![0; 3], |w| *w, ok, &cancelled);
+        assert_eq!(res.unwrap().interrupt(), Some(Interrupt::Cancelled));
+    }
+
+    #[test]
+    fn test_observer_stats() {
+        use crate::observer::Stats;
+
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3), (3, 4, 1)]);
+        let mut ncf = NegCycleFinder::new(&graph).with_observer(Stats::default());
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        assert!(ncf.howard(&mut dist, |w| *w).is_some());
+        let stats = *ncf.observer();
+        assert!(stats.passes >= 1);
+        assert_eq!(stats.relaxations, 4 * stats.passes);
+        assert_eq!(stats.find_cycle_calls, stats.passes);
+        assert_eq!(stats.cycles_found, 1);
+        assert!(stats.updates >= 3);
+        assert_eq!(stats.ratio_improvements, 0);
+
+        let mut stats = Stats::default();
+        let mut ncf = NegCycleFinder::new(&graph).with_observer(&mut stats);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let found = ncf.howard_iter(&mut dist, |w| *w).count();
+        assert_eq!(stats.cycles_found, found);
+
+        let mut stats = Stats::default();
+        let mut ncfq = NegCycleFinderQ::new(&graph).with_observer(&mut stats);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        assert!(ncfq.howard_succ(&mut dist, |w| *w, |_, _| true).is_some());
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let found = ncfq
+            .howard_pred_iter(&mut dist, |w| *w, |_, _| true)
+            .count();
+        assert_eq!(stats.cycles_found, 1 + found);
+        assert_eq!(stats.relaxations, 4 * stats.passes);
+        assert!(stats.find_cycle_calls <= stats.passes);
+    }
+
+    #[test]
+    fn test_every_entry_point_reports_to_observer() {
+        use crate::observer::Stats;
+
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3), (3, 4, 1)]);
+        let dist = || -> HashMap<i32, i32> { (0..5).map(|u| (u, 0)).collect() };
+        let mut ncf = NegCycleFinder::new(&graph).with_observer(Stats::default());
+        assert!(ncf.howard(&mut dist(), |w| *w).is_some());
+        let expected = *ncf.observer();
+
+        let mut ncf = NegCycleFinder::new(&graph).with_observer(Stats::default());
+        assert!(ncf.howard_checked(&mut dist(), |w| *w).unwrap().is_some());
+        assert_eq!(*ncf.observer(), expected);
+        let mut ncf = NegCycleFinder::new(&graph).with_observer(Stats::default());
+        assert!(ncf.try_howard(&mut dist(), |w| *w).unwrap().is_some());
+        assert_eq!(*ncf.observer(), expected);
+
+        let ok = |_: &i32, _: &i32| true;
+        let mut ncfq = NegCycleFinderQ::new(&graph).with_observer(Stats::default());
+        assert!(ncfq.howard_pred(&mut dist(), |w| *w, ok).is_some());
+        let expected = *ncfq.observer();
+        let mut ncfq = NegCycleFinderQ::new(&graph).with_observer(Stats::default());
+        let cycle = ncfq.howard_pred_checked(&mut dist(), |w| *w, ok);
+        assert!(cycle.unwrap().is_some());
+        assert_eq!(*ncfq.observer(), expected);
+        let mut ncfq = NegCycleFinderQ::new(&graph).with_observer(Stats::default());
+        assert!(ncfq
+            .try_find_neg_cycle_pred(&mut dist(), |w| *w, ok)
+            .unwrap()
+            .is_some());
+        assert_eq!(*ncfq.observer(), expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_howard_log_records() {
+        use crate::logging::capture_logs;
+        use log::Level;
+
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)]];
+        let mut ncf = NegCycleFinder::new(&graph);
+        let (cycle, records) = capture_logs(|| ncf.howard(&mut vec![0; 3], |w| *w)).unwrap();
+        assert!(cycle.is_some());
+        let records: Vec<(Level, &str)> = records
+            .iter()
+            .filter(|r| r.target == "digraphx_rs::neg_cycle")
+            .map(|r| (r.level, &*r.message))
+            .collect();
+        assert_eq!(
+            records,
+            [
+                (Level::Trace, "howard: pass 1, 1 updates"),
+                (Level::Trace, "howard: pass 2, 3 updates"),
+                (Level::Debug, "howard: negative cycle of 3 arcs in pass 2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_howard() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0)].into();
+        assert_eq!(
+            ncf.try_howard(&mut dist, |w| *w),
+            Err(Error::MissingNode(2))
+        );
+        dist.insert(2, 0);
+        let cycle = ncf.try_howard(&mut dist, |w| *w).unwrap().unwrap();
+        assert_eq!(cycle.weight, -1);
+
+        let graph = graph_from_edges(&[(0, 1, 4i32), (1, 2, 3)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
+        assert_eq!(ncf.try_howard(&mut dist, |w| *w), Ok(None));
+    }
+
+    #[test]
+    fn test_q_try_variants() {
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)]];
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let ok = |_: &i32, _: &i32| true;
+
+        let mut short = vec![0; 2];
+        let missing = Error::MissingNode(2);
+        let res = ncfq.try_howard_pred(&mut short, |w| *w, ok);
+        assert_eq!(res.unwrap_err(), missing);
+        let res = ncfq.try_howard_succ(&mut short, |w| *w, ok);
+        assert_eq!(res.unwrap_err(), missing);
+        let res = ncfq.try_find_neg_cycle_pred(&mut short, |w| *w, ok);
+        assert_eq!(res.unwrap_err(), missing);
+        let res = ncfq.try_find_neg_cycle_succ(&mut short, |w| *w, ok);
+        assert_eq!(res.unwrap_err(), missing);
+
+        let cycle = ncfq.try_howard_pred(&mut vec![0; 3], |w| *w, ok).unwrap();
+        assert_eq!(cycle.unwrap().iter().sum::<i32>(), -1);
+        let cycle = ncfq.try_howard_succ(&mut vec![0; 3], |w| *w, ok).unwrap();
+        assert_eq!(cycle.unwrap().iter().sum::<i32>(), -1);
+        let pairs = ncfq.try_find_neg_cycle_pred(&mut vec![0; 3], |w| *w, ok);
+        assert_eq!(pairs.unwrap().unwrap().len(), 3);
+        let pairs = ncfq.try_find_neg_cycle_succ(&mut vec![0; 3], |w| *w, ok);
+        assert_eq!(pairs.unwrap().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_try_is_negative_without_cycle() {
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![]];
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist = vec![0; 2];
+        assert_eq!(
+            ncfq.try_howard_pred(&mut dist, |w| *w, |_, _| true),
+            Ok(None)
+        );
+        // Node 0 has no predecessor, so there is no cycle to walk
+        let res = ncfq.try_is_negative(0, &dist, &|w: &i32| *w);
+        assert_eq!(res, Err(Error::InconsistentGraph(0)));
+    }
+
     #[test]
     fn test_q_pred_with_constraint_blocks_all() {
         // Graph has negative cycle, but update_ok blocks every update
@@ -645,6 +2527,91 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_q_struct_edge_payload() {
+        let graph = graph_from_edges(&[
+            (
+                0,
+                1,
+                Arc {
+                    cost: 1,
+                    time: 1,
+                    id: 0,
+                },
+            ),
+            (
+                1,
+                0,
+                Arc {
+                    cost: -3,
+                    time: 1,
+                    id: 1,
+                },
+            ),
+        ]);
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist: HashMap<i32, i64> = HashMap::new();
+        let result = ncfq.howard_pred(&mut dist, |e| i64::from(e.cost), |_, _| true);
+        assert_eq!(result.unwrap().len(), 2);
+        let mut dist: HashMap<i32, i64> = HashMap::new();
+        let result = ncfq.howard_succ(&mut dist, |e| i64::from(e.cost), |_, _| true);
+        assert_eq!(result.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_q_pred_iter() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 0, -2), (2, 3, 1), (3, 2, -3)]);
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let cycles: Vec<_> = ncfq
+            .howard_pred_iter(&mut dist, |w| *w, |_, _| true)
+            .collect();
+        assert_eq!(cycles.len(), 2);
+        for c in &cycles {
+            assert!(c.weight < 0);
+            assert_eq!(c.edges[0].0, c.nodes[0]);
+        }
+    }
+
+    #[test]
+    fn test_q_succ_iter() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3), (2, 3, 0)]);
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let cycles: Vec<_> = ncfq
+            .howard_succ_iter(&mut dist, |w| *w, |_, _| true)
+            .collect();
+        assert_eq!(cycles.len(), 1);
+        let c = &cycles[0];
+        assert_eq!(c.weight, -1);
+        for (i, &(u, v, w)) in c.edges.iter().enumerate() {
+            assert_eq!(u, c.nodes[i]);
+            assert_eq!(v, c.nodes[(i + 1) % c.len()]);
+            assert_eq!(graph[&u][&v], w);
+        }
+    }
+
+    #[test]
+    fn test_q_iter_blocked() {
+        let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3)]);
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        let mut iter = ncfq.howard_pred_iter(&mut dist, |w| *w, |_, _| false);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_q_vec_dist() {
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)]];
+        let mut ncfq = NegCycleFinderQ::new(&graph);
+        let mut dist = vec![0; 3];
+        let cycle = ncfq.howard_pred(&mut dist, |w| *w, |_, _| true).unwrap();
+        assert_eq!(cycle.iter().sum::<i32>(), -1);
+        let mut dist = vec![0; 3];
+        let cycle = ncfq.howard_succ(&mut dist, |w| *w, |_, _| true).unwrap();
+        assert_eq!(cycle.iter().sum::<i32>(), -1);
+    }
+
     #[test]
     fn test_q_empty_graph() {
         let graph: HashMap<i32, HashMap<i32, i32>> = HashMap::new();
@@ -0,0 +1,247 @@
+//! Node-indexed storage for distances, predecessors and other per-node state.
+//!
+//! The algorithms never touch a concrete map type directly; they go through
+//! the [`NodeMap`] trait.  Callers can therefore keep distances in a
+//! `HashMap<N, D>` for arbitrary node keys, or in a plain `Vec<D>` or
+//! [`MapAdapter<D>`] when the nodes are already the dense indices `0..n`, and
+//! skip hashing altogether.
+//!
+//! Internal state such as the predecessor map is stored in the graph's own
+//! [`Graph::Map`](crate::Graph::Map), which is a sparse [`VecMap`] for
+//! index-based graphs and falls back to a `HashMap` otherwise.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::map_adapter::MapAdapter;
+
+/// A mutable mapping from nodes `K` to values `V`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use digraphx_rs::node_map::NodeMap;
+///
+/// fn bump<M: NodeMap<usize, i32>>(m: &mut M, key: usize) {
+///     let v = m.get(key).copied().unwrap_or(0);
+///     m.set(key, v + 1);
+/// }
+///
+/// let mut dense = vec![0, 0, 0];
+/// bump(&mut dense, 1);
+/// assert_eq!(dense, vec![0, 1, 0]);
+///
+/// let mut sparse: HashMap<usize, i32> = HashMap::new();
+/// bump(&mut sparse, 1);
+/// assert_eq!(sparse[&1], 1);
+/// ```
+pub trait NodeMap<K, V> {
+    /// Return a reference to the value stored for `key`, if any.
+    fn get(&self, key: K) -> Option<&V>;
+
+    /// Store `value` for `key`, replacing any previous value.
+    fn set(&mut self, key: K, value: V);
+
+    /// Return `true` if a value is stored for `key`.
+    fn contains_node(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+/// Forwarding implementation, so that a solver that takes its storage by
+/// value can also borrow the caller's map.
+impl<K, V, M: NodeMap<K, V> + ?Sized> NodeMap<K, V> for &mut M {
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        (**self).get(key)
+    }
+
+    #[inline]
+    fn set(&mut self, key: K, value: V) {
+        (**self).set(key, value);
+    }
+}
+
+impl<K: Eq + Hash, V> NodeMap<K, V> for HashMap<K, V> {
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        HashMap::get(self, &key)
+    }
+
+    #[inline]
+    fn set(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+impl<K: Ord, V> NodeMap<K, V> for BTreeMap<K, V> {
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        BTreeMap::get(self, &key)
+    }
+
+    #[inline]
+    fn set(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+/// Dense storage: every index in `0..len()` holds a value.
+///
+/// # Panics
+///
+/// `set` panics if `key` is out of bounds, so the vector must be sized to
+/// the number of nodes up front.
+impl<V> NodeMap<usize, V> for Vec<V> {
+    #[inline]
+    fn get(&self, key: usize) -> Option<&V> {
+        self.as_slice().get(key)
+    }
+
+    #[inline]
+    fn set(&mut self, key: usize, value: V) {
+        self[key] = value;
+    }
+}
+
+/// Dense storage, as for `Vec<V>`.
+///
+/// # Panics
+///
+/// `set` panics if `key` is out of bounds.
+impl<V> NodeMap<usize, V> for MapAdapter<V> {
+    #[inline]
+    fn get(&self, key: usize) -> Option<&V> {
+        self.lst.get(key)
+    }
+
+    #[inline]
+    fn set(&mut self, key: usize, value: V) {
+        self.lst[key] = value;
+    }
+}
+
+/// Sparse map from dense `usize` keys to values, backed by `Vec<Option<V>>`.
+///
+/// Unlike a `Vec<V>`, a `VecMap` distinguishes absent keys and grows on
+/// demand, so it can stand in for a `HashMap<usize, V>` without hashing.
+///
+/// # Example
+///
+/// ```rust
+/// use digraphx_rs::node_map::{NodeMap, VecMap};
+///
+/// let mut m: VecMap<&str> = VecMap::with_capacity(2);
+/// m.set(3, "x");
+/// assert_eq!(m.get(3), Some(&"x"));
+/// assert!(!m.contains_node(0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VecMap<V> {
+    slots: Vec<Option<V>>,
+}
+
+impl<V> VecMap<V> {
+    /// Create an empty map.
+    #[inline]
+    pub fn new() -> Self {
+        VecMap { slots: Vec::new() }
+    }
+
+    /// Create an empty map with room for keys `0..capacity`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut slots = Vec::with_capacity(capacity);
+        slots.resize_with(capacity, || None);
+        VecMap { slots }
+    }
+
+    /// Iterate over the `(key, value)` pairs that are present.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &V)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(k, v)| v.as_ref().map(|v| (k, v)))
+    }
+}
+
+impl<V> Default for VecMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> NodeMap<usize, V> for VecMap<V> {
+    #[inline]
+    fn get(&self, key: usize) -> Option<&V> {
+        self.slots.get(key).and_then(Option::as_ref)
+    }
+
+    #[inline]
+    fn set(&mut self, key: usize, value: V) {
+        if key >= self.slots.len() {
+            self.slots.resize_with(key + 1, || None);
+        }
+        self.slots[key] = Some(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<M: NodeMap<usize, i32>>(m: &mut M) {
+        m.set(1, 7);
+        assert_eq!(m.get(1), Some(&7));
+        assert!(m.contains_node(1));
+        m.set(1, 8);
+        assert_eq!(m.get(1), Some(&8));
+    }
+
+    #[test]
+    fn test_hashmap() {
+        let mut m: HashMap<usize, i32> = HashMap::new();
+        roundtrip(&mut m);
+        assert!(!m.contains_node(0));
+    }
+
+    #[test]
+    fn test_btreemap() {
+        let mut m: BTreeMap<usize, i32> = BTreeMap::new();
+        roundtrip(&mut m);
+        assert!(!m.contains_node(0));
+    }
+
+    #[test]
+    fn test_vec() {
+        let mut m = vec![0; 3];
+        roundtrip(&mut m);
+        assert!(m.contains_node(0));
+        assert!(!m.contains_node(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vec_out_of_range() {
+        let mut m = vec![0; 3];
+        m.set(3, 1);
+    }
+
+    #[test]
+    fn test_map_adapter() {
+        let mut m = MapAdapter::new(vec![0; 3]);
+        roundtrip(&mut m);
+        assert!(!m.contains_node(3));
+    }
+
+    #[test]
+    fn test_vec_map() {
+        let mut m: VecMap<i32> = VecMap::new();
+        roundtrip(&mut m);
+        assert!(!m.contains_node(0));
+        assert!(!m.contains_node(100));
+        m.set(4, 1);
+        assert_eq!(m.iter().collect::<Vec<_>>(), vec![(1, &8), (4, &1)]);
+    }
+}
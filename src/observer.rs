@@ -0,0 +1,206 @@
+//! Observer hooks for instrumenting the searches.
+//!
+//! [`NegCycleFinder`], [`NegCycleFinderQ`] and the parametric solvers report
+//! their progress to an [`Observer`] set with `with_observer`.  The default
+//! [`NoObserver`] ignores every event and compiles away; [`Stats`] counts
+//! them.  Since `Observer` is implemented for `&mut O`, a finder can also
+//! borrow an observer that the caller inspects afterwards.
+//!
+//! [`NegCycleFinder`]: crate::NegCycleFinder
+//! [`NegCycleFinderQ`]: crate::NegCycleFinderQ
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use digraphx_rs::observer::Stats;
+//! use digraphx_rs::{graph_from_edges, NegCycleFinder};
+//!
+//! let graph = graph_from_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+//! let mut stats = Stats::default();
+//! let mut ncf = NegCycleFinder::new(&graph).with_observer(&mut stats);
+//! let mut dist: HashMap<i32, i32> = HashMap::new();
+//! assert!(ncf.howard(&mut dist, |w| *w).is_some());
+//!
+//! assert!(stats.passes >= 1);
+//! assert_eq!(stats.relaxations, 3 * stats.passes);
+//! assert_eq!(stats.cycles_found, 1);
+//! ```
+
+/// Receiver of search events.
+///
+/// Every method has an empty default, so an implementation only overrides
+/// the events it needs.  `N` is the node type of the graph.
+pub trait Observer<N> {
+    /// A relaxation pass starts.
+    fn pass_start(&mut self) {}
+
+    /// A relaxation pass ended after examining `arcs` arcs.  Not called
+    /// when an overflow-checked pass stops with an error.
+    fn pass_end(&mut self, arcs: usize) {
+        let _ = arcs;
+    }
+
+    /// The distance of `node` was updated (lowered by predecessor
+    /// relaxation, raised by successor relaxation).
+    fn distance_updated(&mut self, node: N) {
+        let _ = node;
+    }
+
+    /// The predecessor (or successor) map is searched for cycles.
+    fn find_cycle(&mut self) {}
+
+    /// A negative cycle through `handle` is reported to the caller.
+    fn cycle_found(&mut self, handle: N) {
+        let _ = handle;
+    }
+
+    /// A parametric solver lowered its ratio.
+    fn ratio_improved(&mut self) {}
+}
+
+/// Observer that ignores every event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoObserver;
+
+impl<N> Observer<N> for NoObserver {}
+
+/// Observer counting the events it receives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Relaxation passes started.
+    pub passes: usize,
+    /// Arcs examined by the relaxation passes.
+    pub relaxations: usize,
+    /// Distance updates.
+    pub updates: usize,
+    /// Searches of the predecessor (or successor) map for cycles.
+    pub find_cycle_calls: usize,
+    /// Negative cycles reported.
+    pub cycles_found: usize,
+    /// Ratio improvements of a parametric solver.
+    pub ratio_improvements: usize,
+}
+
+impl<N> Observer<N> for Stats {
+    fn pass_start(&mut self) {
+        self.passes += 1;
+    }
+
+    fn pass_end(&mut self, arcs: usize) {
+        self.relaxations += arcs;
+    }
+
+    fn distance_updated(&mut self, _node: N) {
+        self.updates += 1;
+    }
+
+    fn find_cycle(&mut self) {
+        self.find_cycle_calls += 1;
+    }
+
+    fn cycle_found(&mut self, _handle: N) {
+        self.cycles_found += 1;
+    }
+
+    fn ratio_improved(&mut self) {
+        self.ratio_improvements += 1;
+    }
+}
+
+impl<N, O: Observer<N> + ?Sized> Observer<N> for &mut O {
+    fn pass_start(&mut self) {
+        (**self).pass_start();
+    }
+
+    fn pass_end(&mut self, arcs: usize) {
+        (**self).pass_end(arcs);
+    }
+
+    fn distance_updated(&mut self, node: N) {
+        (**self).distance_updated(node);
+    }
+
+    fn find_cycle(&mut self) {
+        (**self).find_cycle();
+    }
+
+    fn cycle_found(&mut self, handle: N) {
+        (**self).cycle_found(handle);
+    }
+
+    fn ratio_improved(&mut self) {
+        (**self).ratio_improved();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NegCycleFinder;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Start,
+        End(usize),
+        Update(usize),
+        Find,
+        Found(usize),
+    }
+
+    #[derive(Default)]
+    struct Log(Vec<Event>);
+
+    impl Observer<usize> for Log {
+        fn pass_start(&mut self) {
+            self.0.push(Event::Start);
+        }
+        fn pass_end(&mut self, arcs: usize) {
+            self.0.push(Event::End(arcs));
+        }
+        fn distance_updated(&mut self, node: usize) {
+            self.0.push(Event::Update(node));
+        }
+        fn find_cycle(&mut self) {
+            self.0.push(Event::Find);
+        }
+        fn cycle_found(&mut self, handle: usize) {
+            self.0.push(Event::Found(handle));
+        }
+    }
+
+    #[test]
+    fn test_event_order() {
+        use Event::*;
+
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)]];
+        let mut log = Log::default();
+        let mut ncf = NegCycleFinder::new(&graph).with_observer(&mut log);
+        let mut dist = vec![0; 3];
+        assert!(ncf.howard(&mut dist, |w| *w).is_some());
+        assert_eq!(
+            log.0,
+            [
+                Start,
+                Update(0),
+                End(3),
+                Find,
+                Start,
+                Update(1),
+                Update(2),
+                Update(0),
+                End(3),
+                Find,
+                Found(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_observer() {
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(0, -2)]];
+        let mut ncf = NegCycleFinder::new(&graph);
+        assert_eq!(*ncf.observer(), NoObserver);
+        assert!(ncf.howard(&mut vec![0; 2], |w| *w).is_some());
+    }
+}
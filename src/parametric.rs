@@ -1,18 +1,30 @@
-use std::collections::HashMap;
-use std::hash::Hash;
-use std::ops::Add;
-use std::ops::Div;
+use std::ops::{Add, Div, Mul, Sub};
 
+use num::FromPrimitive;
+
+use crate::compare::{Compare, Exact};
+use crate::config::{Interrupt, Outcome, SolverConfig};
+use crate::error::{check_dist, check_limit, Error};
+use crate::macros::as_debug;
+use crate::node_map::NodeMap;
+use crate::observer::{NoObserver, Observer};
+use crate::Cycle;
 use crate::Graph;
+use crate::LogDebug;
 use crate::NegCycleFinder;
+use crate::NegCycleFinderQ;
 use crate::Zero;
 
 /// Interface for parametric network problems.
 ///
+/// `E` is the edge payload stored in the graph and `R` is the ratio (and
+/// distance) domain; it defaults to `E` for problems where the payload is
+/// already the numeric weight.
+///
 /// Implementations provide:
-/// - `distance(ratio, weight)` — computes the effective edge distance given
-///   a parameter `ratio` and the stored edge weight.
-/// - `zero_cancel(cycle)` — given a cycle (list of edge weights), computes
+/// - `distance(ratio, edge)` — computes the effective edge distance given
+///   a parameter `ratio` and the stored edge payload.
+/// - `zero_cancel(cycle)` — given a cycle (list of edge payloads), computes
 ///   the ratio value that would make the cycle's total distance zero.
 ///
 /// # Example
@@ -32,16 +44,160 @@ use crate::Zero;
 ///     }
 /// }
 /// ```
-pub trait ParametricAPI<W> {
+///
+/// With a structured edge payload mapped into a different domain:
+///
+/// ```rust
+/// use digraphx_rs::parametric::ParametricAPI;
+///
+/// #[derive(Clone, Copy)]
+/// struct Arc {
+///     cost: i32,
+///     time: i32,
+/// }
+///
+/// struct CostTime;
+///
+/// impl ParametricAPI<Arc, f64> for CostTime {
+///     fn distance(&self, ratio: &f64, edge: &Arc) -> f64 {
+///         f64::from(edge.cost) - *ratio * f64::from(edge.time)
+///     }
+///     fn zero_cancel(&self, cycle: &[Arc]) -> f64 {
+///         let cost: i32 = cycle.iter().map(|e| e.cost).sum();
+///         let time: i32 = cycle.iter().map(|e| e.time).sum();
+///         f64::from(cost) / f64::from(time)
+///     }
+/// }
+/// ```
+pub trait ParametricAPI<E, R = E> {
     /// Compute the effective edge distance given the current ratio $r$.
     ///
-    /// $$ d'(w, r) = \text{distance}(w, r) $$
-    fn distance(&self, ratio: &W, weight: &W) -> W;
+    /// $$ d'(e, r) = \text{distance}(e, r) $$
+    fn distance(&self, ratio: &R, edge: &E) -> R;
 
     /// Compute the ratio $r^*$ that makes the cycle's total distance zero.
     ///
-    /// $$ \sum_{(u,v) \in C} \text{distance}(w_{uv}, r^*) = 0 $$
-    fn zero_cancel(&self, cycle: &[W]) -> W;
+    /// $$ \sum_{(u,v) \in C} \text{distance}(e_{uv}, r^*) = 0 $$
+    fn zero_cancel(&self, cycle: &[E]) -> R;
+}
+
+/// Minimum mean cycle: the edge payload is the weight and the ratio of a
+/// cycle is its mean weight.
+///
+/// $$ \text{distance}(w, r) = w - r, \qquad
+///    \text{zero\_cancel}(C) = \frac{1}{|C|} \sum_{e \in C} w_e $$
+///
+/// Use an exact domain such as `Ratio<i64>`, or `f64`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use num::rational::Ratio;
+/// use digraphx_rs::graph_from_edges;
+/// use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+///
+/// let graph = graph_from_edges(&[(0, 1, Ratio::from_integer(1i64)), (1, 0, Ratio::from_integer(2))]);
+/// let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+/// let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+/// let result = solver.run(dist, Ratio::from_integer(100));
+/// assert_eq!(result.ratio, Ratio::new(3, 2));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinMeanCycle;
+
+impl<R> ParametricAPI<R, R> for MinMeanCycle
+where
+    R: Add<Output = R> + Sub<Output = R> + Div<Output = R> + Copy + Zero + FromPrimitive,
+{
+    #[inline]
+    fn distance(&self, ratio: &R, edge: &R) -> R {
+        *edge - *ratio
+    }
+
+    fn zero_cancel(&self, cycle: &[R]) -> R {
+        let total = cycle.iter().fold(R::zero(), |acc, &w| acc + w);
+        total / R::from_usize(cycle.len()).unwrap()
+    }
+}
+
+/// Minimum cost-to-time ratio: the edge payload is a `(cost, time)` pair and
+/// the ratio of a cycle is its total cost over its total time.
+///
+/// $$ \text{distance}((c, t), r) = c - r\,t, \qquad
+///    \text{zero\_cancel}(C) = \frac{\sum_{e \in C} c_e}{\sum_{e \in C} t_e} $$
+///
+/// Every cycle must have a positive total time.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use digraphx_rs::graph_from_edges;
+/// use digraphx_rs::parametric::{MaxParametricSolver, MinCostToTimeRatio};
+///
+/// let graph = graph_from_edges(&[(0, 1, (3.0, 1.0)), (1, 0, (1.0, 3.0))]);
+/// let mut solver = MaxParametricSolver::new(&graph, MinCostToTimeRatio);
+/// let dist: HashMap<i32, f64> = HashMap::new();
+/// let result = solver.run(dist, 100.0);
+/// assert_eq!(result.ratio, 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinCostToTimeRatio;
+
+impl<R> ParametricAPI<(R, R), R> for MinCostToTimeRatio
+where
+    R: Add<Output = R> + Sub<Output = R> + Mul<Output = R> + Div<Output = R> + Copy + Zero,
+{
+    #[inline]
+    fn distance(&self, ratio: &R, &(cost, time): &(R, R)) -> R {
+        cost - *ratio * time
+    }
+
+    fn zero_cancel(&self, cycle: &[(R, R)]) -> R {
+        let (cost, time) = cycle
+            .iter()
+            .fold((R::zero(), R::zero()), |(c, t), &(ce, te)| (c + ce, t + te));
+        cost / time
+    }
+}
+
+/// Maximum cost-to-time ratio, solved as a minimum ratio with negated costs.
+///
+/// The edge payload is a `(cost, time)` pair.  Because
+/// [`MaxParametricSolver`] only ever lowers its ratio, this problem works
+/// with $r = -\lambda$: start from the negation of a lower bound on the
+/// maximum cycle ratio $\lambda$, and negate the result.
+///
+/// $$ \text{distance}((c, t), r) = -c - r\,t, \qquad
+///    \text{zero\_cancel}(C) = -\frac{\sum_{e \in C} c_e}{\sum_{e \in C} t_e} $$
+///
+/// Every cycle must have a positive total time.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use num::rational::Ratio;
+/// use digraphx_rs::graph_from_edges;
+/// use digraphx_rs::parametric::{MaxCycleRatio, MaxParametricSolver};
+///
+/// let q = |c: i64, t: i64| (Ratio::from_integer(c), Ratio::from_integer(t));
+/// let graph = graph_from_edges(&[(0, 1, q(3, 1)), (1, 0, q(1, 3)), (1, 1, q(1, 2))]);
+/// let mut solver = MaxParametricSolver::new(&graph, MaxCycleRatio);
+/// let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+/// let result = solver.run(dist, Ratio::from_integer(100)); // lambda >= -100
+/// assert_eq!(-result.ratio, Ratio::from_integer(1));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxCycleRatio;
+
+impl<R> ParametricAPI<(R, R), R> for MaxCycleRatio
+where
+    R: Add<Output = R> + Sub<Output = R> + Mul<Output = R> + Div<Output = R> + Copy + Zero,
+{
+    #[inline]
+    fn distance(&self, ratio: &R, &(cost, time): &(R, R)) -> R {
+        R::zero() - cost - *ratio * time
+    }
+
+    fn zero_cancel(&self, cycle: &[(R, R)]) -> R {
+        R::zero() - MinCostToTimeRatio.zero_cancel(cycle)
+    }
 }
 
 /// Maximum parametric solver.
@@ -52,90 +208,642 @@ pub trait ParametricAPI<W> {
 ///
 /// subject to:
 ///
-/// $$ d\[v\] - d\[u\] \le \text{distance}(w_{uv}, r) \qquad \forall (u,v) \in E $$
+/// $$ d\[v\] - d\[u\] \le \text{distance}(e_{uv}, r) \qquad \forall (u,v) \in E $$
 ///
-/// Generic over any graph type `G` implementing [`Graph`].
+/// Generic over any graph type `G` implementing [`Graph`].  The ratio domain
+/// `R` is chosen by the [`ParametricAPI`] implementation and need not match
+/// the edge payload `G::Edge`.
 ///
 /// # Example
 ///
 /// ```rust
 /// use std::collections::HashMap;
-/// use digraphx_rs::{Graph, graph_from_edges};
-/// use digraphx_rs::parametric::{MaxParametricSolver, ParametricAPI};
-///
-/// struct MinCycle;
-///
-/// impl ParametricAPI<i32> for MinCycle {
-///     fn distance(&self, r: &i32, w: &i32) -> i32 { *w - *r }
-///     fn zero_cancel(&self, cycle: &[i32]) -> i32 {
-///         cycle.iter().sum::<i32>() / cycle.len() as i32
-///     }
-/// }
+/// use num::rational::Ratio;
+/// use digraphx_rs::graph_from_edges;
+/// use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
 ///
+/// let w = |x: i64| Ratio::from_integer(x);
 /// let graph = graph_from_edges(&[
-///     (0, 1, 5i32), (0, 2, 1),
-///     (1, 0, 1), (1, 2, 1),
-///     (2, 1, 1), (2, 0, 1),
+///     (0, 1, w(5)), (0, 2, w(1)),
+///     (1, 0, w(1)), (1, 2, w(1)),
+///     (2, 1, w(1)), (2, 0, w(1)),
 /// ]);
-/// let mut solver = MaxParametricSolver::new(&graph, MinCycle);
-/// let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
-/// let mut ratio = 100i32;
-/// solver.run(&mut dist, &mut ratio);
-/// assert_eq!(ratio, 1);
+/// let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+/// let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+/// let result = solver.run(dist, w(100));
+/// assert_eq!(result.ratio, w(1));
+/// assert!(result.improved);
+/// assert!(result.iterations >= 2);
+/// assert!(!result.cycle.unwrap().is_empty());
 /// ```
-pub struct MaxParametricSolver<'a, G: Graph, P> {
-    ncf: NegCycleFinder<'a, G>,
+pub struct MaxParametricSolver<'a, G: Graph, P, C = Exact, O = NoObserver> {
+    ncf: NegCycleFinder<'a, G, C, O>,
     omega: P,
+    warm_start: bool,
 }
 
-impl<'a, G, P> MaxParametricSolver<'a, G, P>
-where
-    G: Graph,
-    G::Weight: Add<Output = G::Weight> + PartialOrd + Copy + Div<Output = G::Weight> + Zero,
-    G::Node: Copy + Eq + Hash,
-    P: ParametricAPI<G::Weight>,
-{
+impl<'a, G: Graph, P> MaxParametricSolver<'a, G, P> {
     /// Create a new solver.
     pub fn new(graph: &'a G, omega: P) -> Self {
         MaxParametricSolver {
             ncf: NegCycleFinder::new(graph),
             omega,
+            warm_start: false,
+        }
+    }
+}
+
+impl<'a, G: Graph, P, C, O: Observer<G::Node>> MaxParametricSolver<'a, G, P, C, O> {
+    /// Replace the comparison policy used both for relaxation and to decide
+    /// whether a cycle improves the ratio.
+    ///
+    /// With `f64` ratios, a [`Tolerance`](crate::compare::Tolerance) keeps
+    /// rounding noise from passing off the current critical cycle as a new
+    /// negative cycle, which would otherwise end the search early.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use digraphx_rs::compare::Tolerance;
+    /// use digraphx_rs::graph_from_edges;
+    /// use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+    ///
+    /// let graph = graph_from_edges(&[(0, 1, 0.1), (1, 2, 0.2), (2, 0, 0.4), (1, 0, 0.6)]);
+    /// let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle)
+    ///     .with_compare(Tolerance::new(1e-12, 1e-12));
+    /// let result = solver.run(HashMap::new(), 10.0_f64);
+    /// assert!((result.ratio - 0.7 / 3.0).abs() < 1e-12);
+    /// ```
+    pub fn with_compare<C2>(self, cmp: C2) -> MaxParametricSolver<'a, G, P, C2, O> {
+        MaxParametricSolver {
+            ncf: self.ncf.with_compare(cmp),
+            omega: self.omega,
+            warm_start: self.warm_start,
+        }
+    }
+
+    /// Replace the observer, which receives the events of the inner
+    /// [`NegCycleFinder`] and one `ratio_improved` per improving round.
+    ///
+    /// ```rust
+    /// use digraphx_rs::observer::Stats;
+    /// use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+    ///
+    /// let graph: Vec<Vec<(usize, f64)>> = vec![vec![(1, 1.0)], vec![(0, 2.0), (2, 4.0)], vec![(1, 4.0)]];
+    /// let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle).with_observer(Stats::default());
+    /// let result = solver.run(vec![0.0; 3], 10.0);
+    /// assert_eq!(result.ratio, 1.5);
+    /// let stats = solver.observer();
+    /// assert_eq!(stats.ratio_improvements, result.iterations - 1);
+    /// assert!(stats.cycles_found >= stats.ratio_improvements);
+    /// ```
+    pub fn with_observer<O2>(self, obs: O2) -> MaxParametricSolver<'a, G, P, C, O2> {
+        MaxParametricSolver {
+            ncf: self.ncf.with_observer(obs),
+            omega: self.omega,
+            warm_start: self.warm_start,
         }
     }
 
-    /// Run the parametric solver.
+    /// Return the observer.
+    pub fn observer(&self) -> &O {
+        self.ncf.observer()
+    }
+
+    /// Enable or disable warm starts (disabled by default).
+    ///
+    /// A cold start resets every potential to zero before each round.  A
+    /// warm start keeps the potentials left by the previous round, so
+    /// relaxation resumes from an estimate close to the new distances, and
+    /// takes the best cycle of the final predecessor map rather than the
+    /// first one found, so that the ratio still drops in large steps.  The
+    /// predecessor map is rebuilt either way: the previous critical cycle
+    /// has zero weight under the new ratio, so a map that kept it would
+    /// report it again at once and end the search.  The first round of a
+    /// [`run`](Self::run) is always cold.
+    ///
+    /// Warm starts pay off when the rounds need many relaxation passes,
+    /// and cost a little when they converge in a few; see the
+    /// `parametric_*` benches.  With `Ratio` weights the potentials
+    /// accumulate the denominators of every ratio tried, so cold starts are
+    /// usually faster and less prone to overflow.
     ///
-    /// Updates `ratio` in place to the maximum feasible value and returns
-    /// the critical cycle (the cycle that determines the optimal ratio).
-    /// Returns an empty vector if no cycle was necessary.
-    pub fn run(
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use digraphx_rs::graph_from_edges;
+    /// use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+    ///
+    /// let graph = graph_from_edges(&[(0, 1, 1.0), (1, 2, 2.0), (2, 0, 3.0), (1, 0, 5.0)]);
+    /// let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle).with_warm_start(true);
+    /// let result = solver.run(HashMap::new(), 100.0);
+    /// assert_eq!(result.ratio, 2.0);
+    /// ```
+    pub fn with_warm_start(mut self, warm_start: bool) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
+    /// Run the parametric solver starting from the upper bound `ratio`.
+    ///
+    /// Repeatedly runs Howard's algorithm under the current ratio and lowers
+    /// the ratio to the `zero_cancel` value of any negative cycle found,
+    /// until no cycle improves it.  `dist` supplies the storage for the
+    /// node potentials; it is reset to zero before the first round (and
+    /// before every round unless warm starts are enabled) and returned in
+    /// the result.
+    pub fn run<R, M>(&mut self, dist: M, ratio: R) -> ParametricResult<G::Node, G::Edge, R, M>
+    where
+        R: Add<Output = R> + PartialOrd + Copy + Zero + LogDebug,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        C: Compare<R>,
+    {
+        self.run_with_config(dist, ratio, &SolverConfig::new())
+            .into_inner()
+    }
+
+    /// Run the parametric solver under the limits of `config`.
+    ///
+    /// Like [`run`](Self::run); the relaxation passes of all rounds count
+    /// towards the pass limit.  When a limit is hit the solver stops with
+    /// [`Outcome::Interrupted`] holding the best result so far: `ratio` and
+    /// `cycle` are those of the last improvement (still an upper bound on
+    /// the optimum) and `dist` holds the potentials of the unfinished round.
+    pub fn run_with_config<R, M>(
         &mut self,
-        dist: &mut HashMap<G::Node, G::Weight>,
-        ratio: &mut G::Weight,
-    ) -> Vec<G::Weight> {
-        let mut cycle = Vec::new();
-
-        loop {
-            // Reset distances to zero
-            for d in dist.values_mut() {
-                *d = G::Weight::zero();
-            }
-            // Create fresh finder to reset predecessor map
-            let graph = self.ncf.graph();
-            self.ncf = NegCycleFinder::new(graph);
-
-            let get_weight = |w: &G::Weight| self.omega.distance(ratio, w);
-            if let Some(ci) = self.ncf.howard(dist, get_weight) {
-                let ri = self.omega.zero_cancel(&ci);
-                if ri < *ratio {
-                    cycle = ci;
-                    *ratio = ri;
-                    continue;
+        dist: M,
+        ratio: R,
+        config: &SolverConfig,
+    ) -> Outcome<ParametricResult<G::Node, G::Edge, R, M>>
+    where
+        R: Add<Output = R> + PartialOrd + Copy + Zero + LogDebug,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        C: Compare<R>,
+    {
+        let (result, stop) = self.rounds(dist, ratio, config, &mut 0);
+        match stop {
+            Ok(Some(reason)) => Outcome::Interrupted {
+                reason,
+                partial: result,
+            },
+            _ => Outcome::Done(result),
+        }
+    }
+
+    /// Body of [`run_with_config`](Self::run_with_config), adding the
+    /// relaxation passes done to `passes`.  Returns the result so far with
+    /// the limit that stopped the rounds, or the error that broke them off.
+    fn rounds<R, M>(
+        &mut self,
+        mut dist: M,
+        ratio: R,
+        config: &SolverConfig,
+        passes: &mut usize,
+    ) -> Rounds<G::Node, G::Edge, R, M>
+    where
+        R: Add<Output = R> + PartialOrd + Copy + Zero + LogDebug,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        C: Compare<R>,
+    {
+        let mut ratio = ratio;
+        let mut cycle = None;
+        let mut iterations = 0;
+        let _span = span!(DEBUG, "parametric", nodes = self.ncf.graph().num_nodes()).entered();
+
+        let stop = loop {
+            iterations += 1;
+            let round = span!(
+                DEBUG,
+                "parametric_round",
+                round = iterations,
+                ratio = tracing::field::Empty
+            )
+            .entered();
+            record_debug!(round, "ratio", &ratio);
+            if !self.warm_start || iterations == 1 {
+                for u in self.ncf.graph().nodes() {
+                    dist.set(u, R::zero());
+                }
+            }
+            // `howard` starts from an empty predecessor map even on a warm
+            // start: the last critical cycle has zero weight under the new
+            // ratio and would be reported again at once
+            let get_weight = |e: &G::Edge| self.omega.distance(&ratio, e);
+            let found = self
+                .ncf
+                .howard_counted(&mut dist, &get_weight, config, passes);
+            match found {
+                Ok(Outcome::Done(Some(ci))) => {
+                    let best = if self.warm_start {
+                        self.best_pred_cycle(ci, &get_weight)
+                    } else {
+                        Ok((self.cycle_ratio(&ci), ci))
+                    };
+                    let (ri, ci) = match best {
+                        Ok(best) => best,
+                        Err(e) => break Err(e),
+                    };
+                    if self.ncf.compare().less(&ri, &ratio) {
+                        debug!(
+                            "parametric: round {}, ratio improved to {:?}",
+                            iterations,
+                            as_debug(&ri)
+                        );
+                        self.ncf.observer_mut().ratio_improved();
+                        cycle = Some(ci);
+                        ratio = ri;
+                        continue;
+                    }
+                    break Ok(None);
                 }
+                Ok(Outcome::Done(None)) => break Ok(None),
+                Ok(Outcome::Interrupted { reason, .. }) => break Ok(Some(reason)),
+                Err(e) => break Err(e),
             }
-            break;
+        };
+        match stop {
+            Ok(None) => debug!(
+                "parametric: ratio {:?} after {} rounds",
+                as_debug(&ratio),
+                iterations
+            ),
+            Ok(Some(reason)) => debug!("parametric: {} in round {}", reason, iterations),
+            Err(_) => debug!("parametric: broken cycle in round {}", iterations),
+        }
+        let result = ParametricResult {
+            improved: cycle.is_some(),
+            ratio,
+            cycle,
+            iterations,
+            dist,
+        };
+        (result, stop)
+    }
+
+    /// Return the ratio that cancels the weight of `cycle`.
+    fn cycle_ratio<R>(&self, cycle: &Cycle<G::Node, G::Edge, R>) -> R
+    where
+        P: ParametricAPI<G::Edge, R>,
+    {
+        let edges: Vec<G::Edge> = cycle.payloads().copied().collect();
+        self.omega.zero_cancel(&edges)
+    }
+
+    /// Return the cycle of the predecessor map with the lowest ratio, or
+    /// `first` with its ratio if none is lower.
+    ///
+    /// Potentials carried over from the previous round make the search
+    /// close a cycle next to the previous critical one first, whose ratio
+    /// is barely lower; the other cycles of the final map often drop it
+    /// much further.
+    fn best_pred_cycle<R, F>(
+        &self,
+        first: Cycle<G::Node, G::Edge, R>,
+        get_weight: &F,
+    ) -> BestCycle<G::Node, G::Edge, R>
+    where
+        R: Add<Output = R> + Copy + Zero,
+        P: ParametricAPI<G::Edge, R>,
+        F: Fn(&G::Edge) -> R,
+        C: Compare<R>,
+    {
+        let mut best = (self.cycle_ratio(&first), first);
+        for cycle in self.ncf.pred_cycles(get_weight)? {
+            let ratio = self.cycle_ratio(&cycle);
+            if self.ncf.compare().less(&ratio, &best.0) {
+                best = (ratio, cycle);
+            }
+        }
+        Ok(best)
+    }
+
+    /// Like [`run`](Self::run), but fails with [`Error::MissingNode`]
+    /// instead of panicking when `dist` cannot hold every node, such as a
+    /// `Vec` shorter than the node count, and with
+    /// [`Error::InconsistentGraph`] when a cycle found cannot be rebuilt.
+    ///
+    /// ```rust
+    /// use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+    /// use digraphx_rs::Error;
+    ///
+    /// let graph: Vec<Vec<(usize, f64)>> = vec![vec![(1, 1.0)], vec![(0, 2.0)]];
+    /// let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+    /// let err = solver.try_run(vec![0.0; 1], 10.0).unwrap_err();
+    /// assert_eq!(err, Error::MissingNode(1));
+    /// assert_eq!(solver.try_run(vec![0.0; 2], 10.0).unwrap().ratio, 1.5);
+    /// ```
+    pub fn try_run<R, M>(
+        &mut self,
+        dist: M,
+        ratio: R,
+    ) -> TryParametricResult<G::Node, G::Edge, R, M>
+    where
+        R: Add<Output = R> + PartialOrd + Copy + Zero + LogDebug,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        C: Compare<R>,
+    {
+        check_dist(self.ncf.graph(), &dist)?;
+        let (result, stop) = self.rounds(dist, ratio, &SolverConfig::new(), &mut 0);
+        stop?;
+        Ok(result)
+    }
+
+    /// Like [`run_with_config`](Self::run_with_config), with the checks of
+    /// [`try_run`](Self::try_run).  Running out of passes fails with
+    /// [`Error::IterationLimit`]; a deadline or a cancellation still yields
+    /// [`Outcome::Interrupted`] with the best result so far.
+    ///
+    /// ```rust
+    /// use digraphx_rs::config::SolverConfig;
+    /// use digraphx_rs::parametric::{MaxParametricSolver, MinMeanCycle};
+    /// use digraphx_rs::Error;
+    ///
+    /// let graph: Vec<Vec<(usize, f64)>> = vec![vec![(1, 1.0)], vec![(0, 2.0)]];
+    /// let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+    /// let config = SolverConfig::new().with_max_passes(1);
+    /// let res = solver.try_run_with_config(vec![0.0; 2], 10.0, &config);
+    /// assert_eq!(res.unwrap_err(), Error::IterationLimit(1));
+    /// ```
+    pub fn try_run_with_config<R, M>(
+        &mut self,
+        dist: M,
+        ratio: R,
+        config: &SolverConfig,
+    ) -> TryParametricOutcome<G::Node, G::Edge, R, M>
+    where
+        R: Add<Output = R> + PartialOrd + Copy + Zero + LogDebug,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        C: Compare<R>,
+    {
+        check_dist(self.ncf.graph(), &dist)?;
+        let (result, stop) = self.rounds(dist, ratio, config, &mut 0);
+        let outcome = match stop? {
+            Some(reason) => Outcome::Interrupted {
+                reason,
+                partial: result,
+            },
+            None => Outcome::Done(result),
+        };
+        check_limit(outcome, config)
+    }
+}
+
+/// The cycle with the lowest ratio and that ratio, or the node where the
+/// chain of a cycle broke.
+type BestCycle<N, E, R> = Result<(R, Cycle<N, E, R>), Error<N>>;
+
+/// Result of the rounds of a parametric solver, with the limit that
+/// stopped them or the error that broke them off.
+type Rounds<N, E, R, M> = (
+    ParametricResult<N, E, R, M>,
+    Result<Option<Interrupt>, Error<N>>,
+);
+
+/// Result of [`MaxParametricSolver::try_run`] and
+/// [`MaxParametricSolverQ::try_run`].
+pub type TryParametricResult<N, E, R, M> = Result<ParametricResult<N, E, R, M>, Error<N>>;
+
+/// Result of [`MaxParametricSolver::try_run_with_config`].
+pub type TryParametricOutcome<N, E, R, M> = Result<Outcome<ParametricResult<N, E, R, M>>, Error<N>>;
+
+/// Outcome of [`MaxParametricSolver::run`] and [`MaxParametricSolverQ::run`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParametricResult<N, E, R, M> {
+    /// The optimal ratio, or the initial bound if it was already feasible.
+    pub ratio: R,
+    /// The critical cycle that determines `ratio`, or `None` if the initial
+    /// bound was already feasible.  Its `weight` is its total distance under
+    /// the ratio in effect when it was found.
+    pub cycle: Option<Cycle<N, E, R>>,
+    /// Number of Howard rounds run, including the final one that found no
+    /// improving cycle.
+    pub iterations: usize,
+    /// `true` if some cycle lowered the ratio below the initial bound.
+    pub improved: bool,
+    /// Node potentials from the final round.  Unless that round stopped on
+    /// a cycle that did not improve the ratio, they satisfy
+    /// $d[v] - d[u] \le \text{distance}(e_{uv}, r)$ for every arc, which
+    /// certifies that no cycle has a ratio below `ratio`.
+    pub dist: M,
+}
+
+/// Relaxation direction used by [`MaxParametricSolverQ`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Relaxation {
+    /// Lower $d\[v\]$ along incoming arcs, as in
+    /// [`NegCycleFinderQ::howard_pred`].
+    #[default]
+    Pred,
+    /// Raise $d\[u\]$ along outgoing arcs, as in
+    /// [`NegCycleFinderQ::howard_succ`].
+    Succ,
+    /// Switch between `Pred` and `Succ` after every round, starting with
+    /// `Pred`.
+    Alternate,
+}
+
+/// Maximum parametric solver with constrained relaxation.
+///
+/// Solves the same problem as [`MaxParametricSolver`], but runs
+/// [`NegCycleFinderQ`] so that every distance update must be accepted by an
+/// `update_ok(old, new)` predicate, and relaxation can go along predecessor
+/// arcs, successor arcs or alternate between the two (see [`Relaxation`]).
+///
+/// Each round collects the negative cycles found under the current ratio
+/// and moves to the smallest `zero_cancel` value among them.  With
+/// [`with_pick_one_only`](Self::with_pick_one_only) a round stops at the
+/// first cycle that improves the ratio instead.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use num::rational::Ratio;
+/// use digraphx_rs::graph_from_edges;
+/// use digraphx_rs::parametric::{MaxParametricSolverQ, MinMeanCycle, Relaxation};
+///
+/// let w = |x: i64| Ratio::from_integer(x);
+/// let graph = graph_from_edges(&[
+///     (0, 1, w(5)), (0, 2, w(1)),
+///     (1, 0, w(1)), (1, 2, w(1)),
+///     (2, 1, w(1)), (2, 0, w(1)),
+/// ]);
+/// let mut solver = MaxParametricSolverQ::new(&graph, MinMeanCycle)
+///     .with_relaxation(Relaxation::Alternate);
+/// let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+/// let result = solver.run(dist, w(100), |_, _| true);
+/// assert_eq!(result.ratio, w(1));
+/// ```
+pub struct MaxParametricSolverQ<'a, G: Graph, P, C = Exact, O = NoObserver> {
+    ncf: NegCycleFinderQ<'a, G, C, O>,
+    omega: P,
+    relaxation: Relaxation,
+    pick_one_only: bool,
+}
+
+impl<'a, G: Graph, P> MaxParametricSolverQ<'a, G, P> {
+    /// Create a new constrained parametric solver for `graph`.
+    pub fn new(graph: &'a G, omega: P) -> Self {
+        MaxParametricSolverQ {
+            ncf: NegCycleFinderQ::new(graph),
+            omega,
+            relaxation: Relaxation::default(),
+            pick_one_only: false,
         }
-        cycle
+    }
+}
+
+impl<'a, G: Graph, P, C, O: Observer<G::Node>> MaxParametricSolverQ<'a, G, P, C, O> {
+    /// Replace the comparison policy, as in
+    /// [`MaxParametricSolver::with_compare`].
+    pub fn with_compare<C2>(self, cmp: C2) -> MaxParametricSolverQ<'a, G, P, C2, O> {
+        MaxParametricSolverQ {
+            ncf: self.ncf.with_compare(cmp),
+            omega: self.omega,
+            relaxation: self.relaxation,
+            pick_one_only: self.pick_one_only,
+        }
+    }
+
+    /// Replace the observer, as in [`MaxParametricSolver::with_observer`].
+    pub fn with_observer<O2>(self, obs: O2) -> MaxParametricSolverQ<'a, G, P, C, O2> {
+        MaxParametricSolverQ {
+            ncf: self.ncf.with_observer(obs),
+            omega: self.omega,
+            relaxation: self.relaxation,
+            pick_one_only: self.pick_one_only,
+        }
+    }
+
+    /// Return the observer.
+    pub fn observer(&self) -> &O {
+        self.ncf.observer()
+    }
+
+    /// Set the relaxation direction (predecessor by default).
+    pub fn with_relaxation(mut self, relaxation: Relaxation) -> Self {
+        self.relaxation = relaxation;
+        self
+    }
+
+    /// Stop each round at the first cycle that improves the ratio instead
+    /// of searching for the best one (disabled by default).
+    pub fn with_pick_one_only(mut self, pick_one_only: bool) -> Self {
+        self.pick_one_only = pick_one_only;
+        self
+    }
+
+    /// Run the solver starting from the upper bound `ratio`.
+    ///
+    /// Unlike [`MaxParametricSolver::run`], `dist` is not reset: it holds
+    /// the starting potentials, which `update_ok` usually constrains, and is
+    /// updated in place from round to round.
+    pub fn run<R, M, U>(
+        &mut self,
+        dist: M,
+        ratio: R,
+        update_ok: U,
+    ) -> ParametricResult<G::Node, G::Edge, R, M>
+    where
+        R: Add<Output = R> + Sub<Output = R> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        U: Fn(&R, &R) -> bool,
+        C: Compare<R>,
+    {
+        self.rounds(dist, ratio, update_ok).0
+    }
+
+    /// Body of [`run`](Self::run).  Returns the result so far with the
+    /// error that broke the rounds off, if any.
+    fn rounds<R, M, U>(
+        &mut self,
+        mut dist: M,
+        ratio: R,
+        update_ok: U,
+    ) -> Rounds<G::Node, G::Edge, R, M>
+    where
+        R: Add<Output = R> + Sub<Output = R> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        U: Fn(&R, &R) -> bool,
+        C: Compare<R>,
+    {
+        let mut ratio = ratio;
+        let mut cycle = None;
+        let mut iterations = 0;
+        let mut use_succ = self.relaxation == Relaxation::Succ;
+
+        let stop = loop {
+            iterations += 1;
+            let omega = &self.omega;
+            let current = ratio;
+            let get_weight = move |e: &G::Edge| omega.distance(&current, e);
+            let mut cycles = if use_succ {
+                self.ncf.howard_succ_iter(&mut dist, get_weight, &update_ok)
+            } else {
+                self.ncf.howard_pred_iter(&mut dist, get_weight, &update_ok)
+            };
+
+            let mut best = None;
+            while let Some(ci) = cycles.next() {
+                let edges: Vec<G::Edge> = ci.payloads().copied().collect();
+                let ri = omega.zero_cancel(&edges);
+                let bound = best.as_ref().map_or(ratio, |&(rb, _): &(R, _)| rb);
+                if cycles.compare().less(&ri, &bound) {
+                    best = Some((ri, ci));
+                    if self.pick_one_only {
+                        break;
+                    }
+                }
+            }
+            if let Some(e) = cycles.error() {
+                break Err(e);
+            }
+            if self.relaxation == Relaxation::Alternate {
+                use_succ = !use_succ;
+            }
+
+            let Some((ri, ci)) = best else {
+                break Ok(None);
+            };
+            self.ncf.observer_mut().ratio_improved();
+            ratio = ri;
+            cycle = Some(ci);
+        };
+        let result = ParametricResult {
+            improved: cycle.is_some(),
+            ratio,
+            cycle,
+            iterations,
+            dist,
+        };
+        (result, stop)
+    }
+
+    /// Like [`run`](Self::run), but fails with [`Error::MissingNode`]
+    /// instead of panicking when `dist` cannot hold every node, and with
+    /// [`Error::InconsistentGraph`] when a cycle found cannot be rebuilt.
+    pub fn try_run<R, M, U>(
+        &mut self,
+        dist: M,
+        ratio: R,
+        update_ok: U,
+    ) -> TryParametricResult<G::Node, G::Edge, R, M>
+    where
+        R: Add<Output = R> + Sub<Output = R> + PartialOrd + Copy + Zero,
+        M: NodeMap<G::Node, R>,
+        P: ParametricAPI<G::Edge, R>,
+        U: Fn(&R, &R) -> bool,
+        C: Compare<R>,
+    {
+        check_dist(self.ncf.graph(), &dist)?;
+        let (result, stop) = self.rounds(dist, ratio, update_ok);
+        stop?;
+        Ok(result)
     }
 }
 
@@ -143,6 +851,9 @@ where
 mod tests {
     use super::*;
     use crate::graph_from_edges;
+    use crate::testing::{random_edges, Lcg};
+    use num::rational::Ratio;
+    use std::collections::HashMap;
 
     struct MinCycleRatio;
 
@@ -166,10 +877,9 @@ mod tests {
             (2, 0, 1),
         ]);
         let mut solver = MaxParametricSolver::new(&graph, MinCycleRatio);
-        let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
-        let mut ratio = 100i32;
-        solver.run(&mut dist, &mut ratio);
-        assert_eq!(ratio, 1);
+        let dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
+        let result = solver.run(dist, 100i32);
+        assert_eq!(result.ratio, 1);
     }
 
     #[test]
@@ -177,9 +887,9 @@ mod tests {
         // Edge weights: [1, 1, -3] → cycle sum = -1, zero_cancel = -1/3 = 0 (i32)
         let graph = graph_from_edges(&[(0, 1, 1i32), (1, 2, 1), (2, 0, -3)]);
         let mut solver = MaxParametricSolver::new(&graph, MinCycleRatio);
-        let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
-        let mut ratio = 100i32;
-        solver.run(&mut dist, &mut ratio);
+        let dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
+        let result = solver.run(dist, 100i32);
+        let ratio = result.ratio;
         // -1/3 truncates to 0 in integer arithmetic
         assert_eq!(ratio, 0);
     }
@@ -188,9 +898,563 @@ mod tests {
     fn test_parametric_no_cycle() {
         let graph = graph_from_edges(&[(0, 1, 1i32)]);
         let mut solver = MaxParametricSolver::new(&graph, MinCycleRatio);
-        let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0)].into();
-        let mut ratio = 100i32;
-        solver.run(&mut dist, &mut ratio);
-        assert_eq!(ratio, 100);
+        let dist: HashMap<i32, i32> = [(0, 0), (1, 0)].into();
+        let result = solver.run(dist, 100i32);
+        assert_eq!(result.ratio, 100);
+        assert!(result.cycle.is_none());
+        assert!(!result.improved);
+        assert_eq!(result.iterations, 1);
+        assert_eq!(result.dist.len(), 2);
+    }
+
+    #[cfg(not(any(feature = "std", feature = "tracing")))]
+    #[test]
+    fn test_ratio_without_debug() {
+        // Without logging the ratio domain only needs the arithmetic of the
+        // search
+        #[derive(Clone, Copy, PartialEq, PartialOrd)]
+        struct Cost(i32);
+
+        impl Add for Cost {
+            type Output = Cost;
+            fn add(self, rhs: Cost) -> Cost {
+                Cost(self.0 + rhs.0)
+            }
+        }
+
+        impl Zero for Cost {
+            fn zero() -> Cost {
+                Cost(0)
+            }
+        }
+
+        struct MinCost;
+
+        impl ParametricAPI<i32, Cost> for MinCost {
+            fn distance(&self, r: &Cost, w: &i32) -> Cost {
+                Cost(*w - r.0)
+            }
+            fn zero_cancel(&self, cycle: &[i32]) -> Cost {
+                Cost(cycle.iter().sum::<i32>() / cycle.len() as i32)
+            }
+        }
+
+        let graph = graph_from_edges(&[(0, 1, 4i32), (1, 0, 2)]);
+        let mut solver = MaxParametricSolver::new(&graph, MinCost);
+        let result = solver.run(HashMap::new(), Cost(10));
+        assert!(result.ratio == Cost(3));
+        let dist: HashMap<i32, Cost> = [(0, Cost(0)), (1, Cost(0))].into();
+        let result = solver.try_run(dist, Cost(10)).unwrap();
+        assert!(result.ratio == Cost(3));
+    }
+
+    #[test]
+    fn test_parametric_vec_dist() {
+        let graph: Vec<Vec<(usize, i32)>> = vec![vec![(1, 3)], vec![(2, 3)], vec![(0, 0)]];
+        let mut solver = MaxParametricSolver::new(&graph, MinCycleRatio);
+        let mut dist = vec![0; 3];
+        let result = solver.run(&mut dist, 100i32);
+        assert_eq!(result.ratio, 2);
+        assert_eq!(result.cycle.unwrap().len(), 3);
+        // The final potentials are feasible under the optimal ratio
+        for u in 0..3 {
+            for &(v, w) in &graph[u] {
+                assert!(dist[v] - dist[u] <= w - 2);
+            }
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct Arc {
+        cost: i32,
+        time: i32,
+    }
+
+    struct CostTimeRatio;
+
+    impl ParametricAPI<Arc, Ratio<i32>> for CostTimeRatio {
+        fn distance(&self, r: &Ratio<i32>, e: &Arc) -> Ratio<i32> {
+            Ratio::from_integer(e.cost) - *r * e.time
+        }
+        fn zero_cancel(&self, cycle: &[Arc]) -> Ratio<i32> {
+            let cost: i32 = cycle.iter().map(|e| e.cost).sum();
+            let time: i32 = cycle.iter().map(|e| e.time).sum();
+            Ratio::new(cost, time)
+        }
+    }
+
+    #[test]
+    fn test_parametric_struct_edge_payload() {
+        // cycle 0 -> 1 -> 0 has ratio 5/3, cycle 1 -> 2 -> 1 has ratio 4/2
+        let graph = graph_from_edges(&[
+            (0, 1, Arc { cost: 3, time: 2 }),
+            (1, 0, Arc { cost: 2, time: 1 }),
+            (1, 2, Arc { cost: 2, time: 1 }),
+            (2, 1, Arc { cost: 2, time: 1 }),
+        ]);
+        let mut solver = MaxParametricSolver::new(&graph, CostTimeRatio);
+        let dist: HashMap<i32, Ratio<i32>> = HashMap::new();
+        let result = solver.run(dist, Ratio::from_integer(100));
+        let cycle = result.cycle.unwrap();
+        assert_eq!(result.ratio, Ratio::new(5, 3));
+        assert_eq!(cycle.len(), 2);
+        let mut nodes = cycle.nodes.clone();
+        nodes.sort();
+        assert_eq!(nodes, vec![0, 1]);
+    }
+
+    fn q(x: i64) -> Ratio<i64> {
+        Ratio::from_integer(x)
+    }
+
+    #[test]
+    fn test_min_mean_cycle_ratio() {
+        // Means: 0 -> 1 -> 0 is 7/2, 1 -> 2 -> 3 -> 1 is 4/3, 3 -> 3 is 2
+        let graph = graph_from_edges(&[
+            (0, 1, q(3)),
+            (1, 0, q(4)),
+            (1, 2, q(1)),
+            (2, 3, q(1)),
+            (3, 1, q(2)),
+            (3, 3, q(2)),
+        ]);
+        let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+        let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+        let result = solver.run(dist, q(100));
+        let cycle = result.cycle.unwrap();
+        assert_eq!(result.ratio, Ratio::new(4, 3));
+        assert_eq!(cycle.len(), 3);
+    }
+
+    #[test]
+    fn test_min_mean_cycle_f64() {
+        let graph = graph_from_edges(&[(0, 1, 1.0), (1, 2, 2.0), (2, 0, 3.0), (2, 2, 2.5)]);
+        let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+        let dist: HashMap<i32, f64> = HashMap::new();
+        let result = solver.run(dist, 100.0);
+        assert_eq!(result.ratio, 2.0);
+    }
+
+    #[test]
+    fn test_min_cost_to_time_ratio() {
+        // Ratios: 0 <-> 1 is 6/2, 1 <-> 2 is 3/4
+        let graph = graph_from_edges(&[
+            (0, 1, (q(5), q(1))),
+            (1, 0, (q(1), q(1))),
+            (1, 2, (q(1), q(2))),
+            (2, 1, (q(2), q(2))),
+        ]);
+        let mut solver = MaxParametricSolver::new(&graph, MinCostToTimeRatio);
+        let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+        let result = solver.run(dist, q(100));
+        let cycle = result.cycle.unwrap();
+        assert_eq!(result.ratio, Ratio::new(3, 4));
+        let mut nodes = cycle.nodes.clone();
+        nodes.sort();
+        assert_eq!(nodes, vec![1, 2]);
+
+        let graph = graph_from_edges(&[(0, 1, (5.0, 1.0)), (1, 0, (1.0, 3.0))]);
+        let mut solver = MaxParametricSolver::new(&graph, MinCostToTimeRatio);
+        let dist: HashMap<i32, f64> = HashMap::new();
+        let result = solver.run(dist, 100.0);
+        assert_eq!(result.ratio, 1.5);
+    }
+
+    #[test]
+    fn test_max_cycle_ratio() {
+        let graph = graph_from_edges(&[
+            (0, 1, (q(5), q(1))),
+            (1, 0, (q(1), q(1))),
+            (1, 2, (q(1), q(2))),
+            (2, 1, (q(2), q(2))),
+        ]);
+        let mut solver = MaxParametricSolver::new(&graph, MaxCycleRatio);
+        let dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+        let result = solver.run(dist, q(100));
+        let cycle = result.cycle.unwrap();
+        assert_eq!(-result.ratio, q(3));
+        let mut nodes = cycle.nodes.clone();
+        nodes.sort();
+        assert_eq!(nodes, vec![0, 1]);
+
+        let graph = graph_from_edges(&[(0, 1, (5.0, 1.0)), (1, 0, (1.0, 3.0)), (1, 1, (0.5, 1.0))]);
+        let mut solver = MaxParametricSolver::new(&graph, MaxCycleRatio);
+        let dist: HashMap<i32, f64> = HashMap::new();
+        let result = solver.run(dist, 100.0);
+        assert_eq!(-result.ratio, 1.5);
+    }
+
+    #[test]
+    fn test_builtins_match_cycle_ratio() {
+        use crate::cycle_ratio::{max_cycle_ratio, min_cycle_ratio};
+        let graph = graph_from_edges(&[
+            (0, 1, (q(4), q(3))),
+            (1, 2, (q(-2), q(1))),
+            (2, 0, (q(7), q(2))),
+            (2, 3, (q(1), q(1))),
+            (3, 1, (q(0), q(5))),
+            (3, 3, (q(3), q(4))),
+        ]);
+        let min = min_cycle_ratio(&graph, |&p| p).unwrap().unwrap().ratio;
+        let max = max_cycle_ratio(&graph, |&p| p).unwrap().unwrap().ratio;
+
+        let mut solver = MaxParametricSolver::new(&graph, MinCostToTimeRatio);
+        let mut dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+        let result = solver.run(&mut dist, q(100));
+        assert_eq!(result.ratio, min);
+
+        let mut solver = MaxParametricSolver::new(&graph, MaxCycleRatio);
+        let result = solver.run(&mut dist, q(100));
+        assert_eq!(-result.ratio, max);
+    }
+
+    #[test]
+    fn test_warm_start_matches_cold() {
+        let mut rng = Lcg(3);
+        for _ in 0..50 {
+            let edges = random_edges(&mut rng, 10, |rng| {
+                (q(rng.next(21) as i64 - 10), q(1 + rng.next(4) as i64))
+            });
+            let graph = graph_from_edges(&edges);
+
+            let mut cold = MaxParametricSolver::new(&graph, MinCostToTimeRatio);
+            let cold = cold.run(HashMap::new(), q(100));
+            let mut warm =
+                MaxParametricSolver::new(&graph, MinCostToTimeRatio).with_warm_start(true);
+            let warm = warm.run(HashMap::new(), q(100));
+            assert_eq!(warm.ratio, cold.ratio);
+            assert_eq!(warm.improved, cold.improved);
+            if let Some(cycle) = &warm.cycle {
+                let arcs: Vec<_> = cycle.payloads().copied().collect();
+                assert_eq!(MinCostToTimeRatio.zero_cancel(&arcs), warm.ratio);
+            }
+
+            // The warm potentials are a certificate for the optimal ratio
+            for u in graph.nodes() {
+                for (v, (c, t)) in graph.neighbors(u) {
+                    let du = warm.dist.get(&u).copied().unwrap_or_default();
+                    let dv = warm.dist.get(&v).copied().unwrap_or_default();
+                    assert!(dv - du <= c - warm.ratio * t);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_q_matches_unconstrained() {
+        let mut rng = Lcg(11);
+        for _ in 0..30 {
+            let edges = random_edges(&mut rng, 8, |rng| {
+                (q(rng.next(21) as i64 - 10), q(1 + rng.next(3) as i64))
+            });
+            let graph = graph_from_edges(&edges);
+
+            let mut solver = MaxParametricSolver::new(&graph, MinCostToTimeRatio);
+            let expected = solver.run(HashMap::new(), q(100));
+            for relaxation in [Relaxation::Pred, Relaxation::Succ, Relaxation::Alternate] {
+                for pick_one_only in [false, true] {
+                    let mut solver = MaxParametricSolverQ::new(&graph, MinCostToTimeRatio)
+                        .with_relaxation(relaxation)
+                        .with_pick_one_only(pick_one_only);
+                    let result = solver.run(HashMap::new(), q(100), |_, _| true);
+                    assert_eq!(result.ratio, expected.ratio);
+                    assert_eq!(result.improved, expected.improved);
+                    if let Some(c) = result.cycle {
+                        let edges: Vec<_> = c.payloads().copied().collect();
+                        assert_eq!(MinCostToTimeRatio.zero_cancel(&edges), result.ratio);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_q_update_ok_blocks_cycles() {
+        let graph = graph_from_edges(&[(0, 1, q(1)), (1, 0, q(3)), (1, 2, q(0)), (2, 1, q(0))]);
+        let mut solver = MaxParametricSolverQ::new(&graph, MinMeanCycle);
+        let result = solver.run(HashMap::new(), q(10), |_, _| false);
+        assert_eq!(result.ratio, q(10));
+        assert!(!result.improved);
+        assert_eq!(result.iterations, 1);
+
+        // The starting potentials are kept between rounds
+        let mut solver = MaxParametricSolverQ::new(&graph, MinMeanCycle);
+        let mut dist: HashMap<i32, Ratio<i64>> = HashMap::new();
+        let result = solver.run(&mut dist, q(10), |_, _| true);
+        assert_eq!(result.ratio, q(0));
+        assert!(result.iterations >= 2);
+        for u in graph.nodes() {
+            for (v, w) in graph.neighbors(u) {
+                let du = dist.get(&u).copied().unwrap_or_default();
+                let dv = dist.get(&v).copied().unwrap_or_default();
+                assert!(dv - du <= w - q(0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tolerance_matches_karp() {
+        use crate::compare::Tolerance;
+        use crate::min_mean_cycle;
+
+        // Means are multiples of 0.1 over short cycles, so an exact
+        // comparison often sees the critical cycle again as slightly negative
+        let mut rng = Lcg(5);
+        for _ in 0..200 {
+            let n = 3 + rng.next(12);
+            let mut edges = Vec::new();
+            for u in 0..n {
+                edges.push((u, (u + 1) % n, 0.1 * f64::from(rng.next(100))));
+                for _ in 0..rng.next(3) {
+                    edges.push((u, rng.next(n), 0.1 * f64::from(rng.next(100))));
+                }
+            }
+            let graph = graph_from_edges(&edges);
+            let karp = min_mean_cycle(&graph, |w| *w).unwrap().mean;
+
+            let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle)
+                .with_compare(Tolerance::new(1e-9, 1e-9));
+            let result = solver.run(HashMap::new(), 100.0);
+            assert!((result.ratio - karp).abs() < 1e-9);
+
+            let mut solver = MaxParametricSolverQ::new(&graph, MinMeanCycle)
+                .with_compare(Tolerance::new(1e-9, 1e-9));
+            let result = solver.run(HashMap::new(), 100.0, |_, _| true);
+            assert!((result.ratio - karp).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_try_run_short_dist() {
+        let graph: Vec<Vec<(usize, Ratio<i64>)>> =
+            vec![vec![(1, q(1))], vec![(2, q(2))], vec![(0, q(3))]];
+        let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+        let err = solver.try_run(vec![q(0); 2], q(100)).unwrap_err();
+        assert_eq!(err, Error::MissingNode(2));
+        assert_eq!(solver.try_run(vec![q(0); 3], q(100)).unwrap().ratio, q(2));
+
+        let mut solver = MaxParametricSolverQ::new(&graph, MinMeanCycle);
+        let err = solver.try_run(vec![q(0); 1], q(100), |_, _| true);
+        assert_eq!(err.unwrap_err(), Error::MissingNode(1));
+        let res = solver.try_run(vec![q(0); 3], q(100), |_, _| true).unwrap();
+        assert_eq!(res.ratio, q(2));
+    }
+
+    #[test]
+    fn test_run_with_config() {
+        use crate::config::Interrupt;
+
+        let mut rng = Lcg(11);
+        for _ in 0..50 {
+            let n = 2 + rng.next(8) as usize;
+            let mut graph: Vec<Vec<(usize, Ratio<i64>)>> = vec![Vec::new(); n];
+            for (u, arcs) in graph.iter_mut().enumerate() {
+                arcs.push(((u + 1) % n, q(rng.next(20) as i64)));
+                for _ in 0..rng.next(3) {
+                    arcs.push((rng.next(n as u32) as usize, q(rng.next(20) as i64)));
+                }
+            }
+            let mut solver = MaxParametricSolver::new(&graph, MinMeanCycle);
+            let expected = solver.run(vec![q(0); n], q(100));
+            let outcome = solver.run_with_config(vec![q(0); n], q(100), &SolverConfig::new());
+            assert_eq!(outcome.done().as_ref(), Some(&expected));
+
+            // Any pass budget yields an upper bound on the optimum
+            for max_passes in 0..4 {
+                let config = SolverConfig::new().with_max_passes(max_passes);
+                let checked = solver.try_run_with_config(vec![q(0); n], q(100), &config);
+                match solver.run_with_config(vec![q(0); n], q(100), &config) {
+                    Outcome::Done(result) => {
+                        assert_eq!(result, expected);
+                        assert_eq!(checked.unwrap().done(), Some(result));
+                    }
+                    Outcome::Interrupted { reason, partial } => {
+                        assert_eq!(reason, Interrupt::IterationLimit);
+                        assert!(partial.ratio >= expected.ratio);
+                        assert_eq!(checked, Err(Error::IterationLimit(max_passes)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_q_observer() {
+        use crate::observer::Stats;
+
+        let graph = graph_from_edges(&[
+            (0, 1, q(5)),
+            (0, 2, q(1)),
+            (1, 0, q(1)),
+            (1, 2, q(1)),
+            (2, 1, q(1)),
+            (2, 0, q(1)),
+        ]);
+        let mut solver = MaxParametricSolverQ::new(&graph, MinMeanCycle)
+            .with_relaxation(Relaxation::Alternate)
+            .with_observer(Stats::default());
+        let result = solver.run(HashMap::new(), q(100), |_, _| true);
+        assert_eq!(result.ratio, q(1));
+        let stats = solver.observer();
+        assert_eq!(stats.ratio_improvements, result.iterations - 1);
+        assert!(stats.cycles_found >= stats.ratio_improvements);
+        assert_eq!(stats.relaxations, 6 * stats.passes);
+    }
+
+    /// Subscriber recording every span with its parent and fields.
+    #[cfg(feature = "tracing")]
+    mod recorder {
+        use std::collections::HashMap;
+        use std::fmt;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        #[derive(Debug, Clone)]
+        pub struct SpanData {
+            pub name: &'static str,
+            pub parent: Option<usize>,
+            pub fields: HashMap<&'static str, String>,
+        }
+
+        #[derive(Default)]
+        struct State {
+            spans: Vec<SpanData>,
+            stack: Vec<usize>,
+        }
+
+        #[derive(Clone, Default)]
+        pub struct Recorder(Arc<Mutex<State>>);
+
+        struct Fields<'a>(&'a mut HashMap<&'static str, String>);
+
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                self.0.insert(field.name(), format!("{value:?}"));
+            }
+        }
+
+        impl Recorder {
+            pub fn spans(&self) -> Vec<SpanData> {
+                self.0.lock().unwrap().spans.clone()
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+                let mut state = self.0.lock().unwrap();
+                let mut fields = HashMap::new();
+                attrs.record(&mut Fields(&mut fields));
+                let parent = state.stack.last().copied();
+                state.spans.push(SpanData {
+                    name: attrs.metadata().name(),
+                    parent,
+                    fields,
+                });
+                Id::from_u64(state.spans.len() as u64)
+            }
+
+            fn record(&self, id: &Id, values: &Record<'_>) {
+                let mut state = self.0.lock().unwrap();
+                let span = &mut state.spans[id.into_u64() as usize - 1];
+                values.record(&mut Fields(&mut span.fields));
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, _: &Event<'_>) {}
+
+            fn enter(&self, id: &Id) {
+                let mut state = self.0.lock().unwrap();
+                state.stack.push(id.into_u64() as usize - 1);
+            }
+
+            fn exit(&self, _: &Id) {
+                self.0.lock().unwrap().stack.pop();
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_spans() {
+        let graph: Vec<Vec<(usize, f64)>> =
+            vec![vec![(1, 1.0)], vec![(0, 2.0), (2, 4.0)], vec![(1, 4.0)]];
+        let recorder = recorder::Recorder::default();
+        let result = tracing::subscriber::with_default(recorder.clone(), || {
+            MaxParametricSolver::new(&graph, MinMeanCycle).run(vec![0.0; 3], 10.0)
+        });
+        assert_eq!(result.ratio, 1.5);
+
+        let spans = recorder.spans();
+        let named = |name: &str| -> Vec<usize> {
+            (0..spans.len())
+                .filter(|&i| spans[i].name == name)
+                .collect()
+        };
+        let solve = named("parametric");
+        assert_eq!(solve.len(), 1);
+        assert_eq!(spans[solve[0]].fields["nodes"], "3");
+
+        let rounds = named("parametric_round");
+        assert_eq!(rounds.len(), result.iterations);
+        for (k, &i) in rounds.iter().enumerate() {
+            assert_eq!(spans[i].parent, Some(solve[0]));
+            assert_eq!(spans[i].fields["round"], (k + 1).to_string());
+        }
+        // Each round records the ratio it runs under
+        let ratios: Vec<&str> = rounds.iter().map(|&i| &*spans[i].fields["ratio"]).collect();
+        assert_eq!(ratios, ["10.0", "4.0", "1.5"]);
+
+        let howards = named("howard");
+        assert_eq!(howards.len(), rounds.len());
+        for (&h, &r) in howards.iter().zip(&rounds) {
+            assert_eq!(spans[h].parent, Some(r));
+            assert_eq!(spans[h].fields["nodes"], "3");
+            let passes: usize = spans[h].fields["passes"].parse().unwrap();
+            let relaxes: Vec<usize> = named("relax")
+                .into_iter()
+                .filter(|&p| spans[p].parent == Some(h))
+                .collect();
+            assert_eq!(relaxes.len(), passes);
+            for (k, &p) in relaxes.iter().enumerate() {
+                assert_eq!(spans[p].fields["pass"], (k + 1).to_string());
+                assert!(spans[p].fields.contains_key("updates"));
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_log_records() {
+        use crate::logging::capture_logs;
+
+        let graph: Vec<Vec<(usize, f64)>> =
+            vec![vec![(1, 1.0)], vec![(0, 2.0), (2, 4.0)], vec![(1, 4.0)]];
+        let (result, records) =
+            capture_logs(|| MaxParametricSolver::new(&graph, MinMeanCycle).run(vec![0.0; 3], 10.0))
+                .unwrap();
+        assert_eq!(result.ratio, 1.5);
+        let messages: Vec<&str> = records
+            .iter()
+            .filter(|r| r.target == "digraphx_rs::parametric")
+            .map(|r| &*r.message)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "parametric: round 1, ratio improved to 4.0",
+                "parametric: round 2, ratio improved to 1.5",
+                "parametric: ratio 1.5 after 3 rounds",
+            ]
+        );
+        assert!(records
+            .iter()
+            .any(|r| r.target == "digraphx_rs::neg_cycle" && r.level == log::Level::Trace));
     }
 }
@@ -1,7 +1,24 @@
 //! Prelude module for convenient imports.
 
+pub use crate::compare::{Compare, Exact, Tolerance};
+pub use crate::config::{Interrupt, Outcome, SolverConfig};
+pub use crate::csr::CsrGraph;
+pub use crate::cycle::Cycle;
+pub use crate::cycle_ratio::{max_cycle_ratio, min_cycle_ratio, RatioCycle, ZeroTimeCycle};
+pub use crate::difference_constraints::{DifferenceConstraints, Infeasible};
+pub use crate::error::Error;
+pub use crate::indexer::{IndexedGraph, NodeIndexer};
+pub use crate::karp::{min_mean_cycle, MeanCycle};
 pub use crate::map_adapter::MapAdapter;
 pub use crate::neg_cycle::NegCycleFinder;
 pub use crate::neg_cycle::NegCycleFinderQ;
-pub use crate::parametric::{MaxParametricSolver, ParametricAPI};
-pub use crate::Graph;
+pub use crate::node_map::{NodeMap, VecMap};
+pub use crate::observer::{NoObserver, Observer, Stats};
+pub use crate::parametric::{
+    MaxCycleRatio, MaxParametricSolver, MaxParametricSolverQ, MinCostToTimeRatio, MinMeanCycle,
+    ParametricAPI, Relaxation,
+};
+pub use crate::reverse::Reversed;
+pub use crate::shortest_path::{bellman_ford, ShortestPathError, ShortestPaths};
+pub use crate::views::{FilterEdges, FilterNodes, InducedSubgraph, MapWeights};
+pub use crate::{Graph, ReverseGraph};
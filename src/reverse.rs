@@ -0,0 +1,260 @@
+//! Reversed view of a graph.
+//!
+//! [`Reversed`] stores the incoming edges of every node of a [`Graph`] in
+//! compressed sparse row form.  It implements [`Graph`] with every edge
+//! flipped, so that running an algorithm on it searches backwards, and
+//! [`ReverseGraph`], whose in-neighbors are the out-neighbors of the wrapped
+//! graph.
+//!
+//! # Example
+//!
+//! ```rust
+//! use digraphx_rs::{bellman_ford, Graph, Reversed};
+//!
+//! // Shortest distances *to* node 2 are shortest distances from node 2 in
+//! // the reversed graph.
+//! let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1), (2, 5)], vec![(2, 1)], vec![]];
+//! let r = Reversed::new(&g);
+//! assert_eq!(r.neighbors(2).collect::<Vec<_>>(), vec![(0, 5), (1, 1)]);
+//!
+//! let to_target = bellman_ford(&r, 2, |w| *w).unwrap();
+//! assert_eq!(to_target.distance(0), Some(2));
+//! ```
+
+use std::iter::Copied;
+use std::slice::Iter;
+
+use crate::node_map::NodeMap;
+use crate::{Graph, ReverseGraph};
+
+/// A graph with all edges of `G` reversed.
+///
+/// The incoming edges are collected once by [`Reversed::new`].  Nodes that
+/// only appear as neighbors in `G` are nodes of the view too, listed after
+/// the nodes of `G`.  `G` may be a reference, so that the view borrows the
+/// graph instead of owning it.
+pub struct Reversed<G: Graph> {
+    graph: G,
+    nodes: Vec<G::Node>,
+    index: G::Map<usize>,
+    offsets: Vec<usize>,
+    arcs: Vec<(G::Node, G::Edge)>,
+}
+
+impl<G: Graph> Reversed<G> {
+    /// Collect the incoming edges of every node of `graph`.
+    ///
+    /// The incoming edges of a node are listed in the order of their source
+    /// in `graph.nodes()`.
+    ///
+    /// Complexity: $O(n + m)$ time and space.
+    pub fn new(graph: G) -> Self {
+        let mut index: G::Map<usize> = graph.node_map();
+        let mut nodes = Vec::new();
+        for u in graph.nodes() {
+            index.set(u, nodes.len());
+            nodes.push(u);
+        }
+
+        let mut offsets = vec![0; nodes.len() + 1];
+        for u in graph.nodes() {
+            for (v, _) in graph.neighbors(u) {
+                let j = match index.get(v) {
+                    Some(&j) => j,
+                    None => {
+                        index.set(v, nodes.len());
+                        nodes.push(v);
+                        offsets.push(0);
+                        nodes.len() - 1
+                    }
+                };
+                offsets[j + 1] += 1;
+            }
+        }
+        let num_nodes = nodes.len();
+        for j in 0..num_nodes {
+            offsets[j + 1] += offsets[j];
+        }
+
+        let mut next = offsets.clone();
+        let mut slots: Vec<Option<(G::Node, G::Edge)>> = vec![None; offsets[num_nodes]];
+        for u in graph.nodes() {
+            for (v, e) in graph.neighbors(u) {
+                if let Some(&j) = index.get(v) {
+                    slots[next[j]] = Some((u, e));
+                    next[j] += 1;
+                }
+            }
+        }
+        let arcs = slots.into_iter().flatten().collect();
+
+        Reversed {
+            graph,
+            nodes,
+            index,
+            offsets,
+            arcs,
+        }
+    }
+
+    /// Return the wrapped graph.
+    #[inline]
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Consume the view and return the wrapped graph.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+
+    /// Return the number of incoming edges of `node`.
+    pub fn in_degree(&self, node: G::Node) -> usize {
+        self.incoming(node).len()
+    }
+
+    fn incoming(&self, node: G::Node) -> &[(G::Node, G::Edge)] {
+        self.index.get(node).map_or(&[][..], |&j| {
+            &self.arcs[self.offsets[j]..self.offsets[j + 1]]
+        })
+    }
+}
+
+impl<G: Graph> Graph for Reversed<G> {
+    type Node = G::Node;
+    type Edge = G::Edge;
+    type Nodes<'a>
+        = Copied<Iter<'a, G::Node>>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = Copied<Iter<'a, (G::Node, G::Edge)>>
+    where
+        Self: 'a;
+    type Map<V> = G::Map<V>;
+
+    #[inline]
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.nodes.iter().copied()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_> {
+        self.incoming(node).iter().copied()
+    }
+
+    #[inline]
+    fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn node_map<V>(&self) -> Self::Map<V> {
+        self.graph.node_map()
+    }
+}
+
+impl<G: Graph> ReverseGraph for Reversed<G> {
+    type InNeighbors<'a>
+        = G::Neighbors<'a>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn in_neighbors(&self, node: Self::Node) -> Self::InNeighbors<'_> {
+        self.graph.neighbors(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bellman_ford, graph_from_edges, CsrGraph, NegCycleFinder};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_reversed_vec() {
+        let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1), (2, 2)], vec![(2, 3)], vec![(0, 4)]];
+        let r = Reversed::new(&g);
+        assert_eq!(r.num_nodes(), 3);
+        assert_eq!(r.nodes().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(r.neighbors(0).collect::<Vec<_>>(), vec![(2, 4)]);
+        assert_eq!(r.neighbors(1).collect::<Vec<_>>(), vec![(0, 1)]);
+        assert_eq!(r.neighbors(2).collect::<Vec<_>>(), vec![(0, 2), (1, 3)]);
+        assert_eq!(r.in_degree(2), 2);
+        assert_eq!(r.in_degree(9), 0);
+        assert!(r.neighbors(9).next().is_none());
+        for u in 0..3 {
+            assert!(r.in_neighbors(u).eq(g.neighbors(u)));
+        }
+    }
+
+    #[test]
+    fn test_reversed_hashmap() {
+        let g = graph_from_edges(&[("a", "b", 1), ("a", "c", 2), ("c", "b", 3)]);
+        let r = Reversed::new(&g);
+        let mut into_b: Vec<_> = r.neighbors("b").collect();
+        into_b.sort();
+        assert_eq!(into_b, vec![("a", 1), ("c", 3)]);
+        assert!(r.neighbors("a").next().is_none());
+        assert_eq!(r.inner().num_nodes(), 3);
+    }
+
+    #[test]
+    fn test_reversed_twice() {
+        let g = CsrGraph::from_edges(&[(0, 1, 1), (1, 2, 2), (0, 2, 3), (2, 0, 4)]);
+        let rr = Reversed::new(Reversed::new(g.clone()));
+        for u in g.nodes() {
+            let mut expected: Vec<_> = g.neighbors(u).collect();
+            let mut actual: Vec<_> = rr.neighbors(u).collect();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+        assert_eq!(rr.into_inner().into_inner(), g);
+    }
+
+    #[test]
+    fn test_reversed_keeps_unlisted_targets() {
+        // Node 5 is not listed by the Vec graph
+        let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1), (5, 2)], vec![]];
+        let r = Reversed::new(&g);
+        assert_eq!(r.nodes().collect::<Vec<_>>(), vec![0, 1, 5]);
+        assert_eq!(r.num_nodes(), 3);
+        assert_eq!(r.neighbors(1).collect::<Vec<_>>(), vec![(0, 1)]);
+        assert_eq!(r.neighbors(5).collect::<Vec<_>>(), vec![(0, 2)]);
+        assert!(r.in_neighbors(5).next().is_none());
+    }
+
+    #[test]
+    fn test_reversed_sink_not_a_key() {
+        // A plain nested HashMap has no entry for the sink 1
+        let g: HashMap<i32, HashMap<i32, i32>> = [(0, [(1, 3)].into())].into();
+        let r = Reversed::new(&g);
+        let mut nodes: Vec<_> = r.nodes().collect();
+        nodes.sort();
+        assert_eq!(nodes, vec![0, 1]);
+        assert_eq!(r.neighbors(1).collect::<Vec<_>>(), vec![(0, 3)]);
+        assert_eq!(r.in_degree(0), 0);
+
+        let from_sink = bellman_ford(&r, 1, |w| *w).unwrap();
+        assert_eq!(from_sink.distance(0), Some(3));
+    }
+
+    #[test]
+    fn test_reversed_neg_cycle() {
+        let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1)], vec![(2, 1)], vec![(0, -3)], vec![]];
+        let r = Reversed::new(&g);
+        let mut ncf = NegCycleFinder::new(&r);
+        let cycle = ncf.howard(&mut vec![0; 4], |w| *w).unwrap();
+        assert_eq!(cycle.weight, -1);
+        // Every arc of the cycle is an arc of `g` read backwards
+        for &(u, v, e) in &cycle.edges {
+            assert!(g.neighbors(v).any(|arc| arc == (u, e)));
+        }
+
+        let mut dist: HashMap<usize, i32> = HashMap::new();
+        assert!(NegCycleFinder::new(&r).howard(&mut dist, |w| *w).is_some());
+    }
+}
@@ -0,0 +1,293 @@
+//! Single-source shortest paths with the Bellman–Ford algorithm.
+//!
+//! [`bellman_ford`] works on any [`Graph`] and, like the negative cycle
+//! finders, maps edge payloads into a distance domain through a
+//! `get_weight` closure.  Only nodes reachable from the source receive a
+//! distance.  A negative cycle reachable from the source is reported as a
+//! [`ShortestPathError::NegativeCycle`] error carrying the offending
+//! [`Cycle`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Add;
+
+use crate::error::Error;
+use crate::neg_cycle::find_cycles;
+use crate::node_map::NodeMap;
+use crate::Cycle;
+use crate::Graph;
+use crate::Zero;
+
+/// Shortest-path tree rooted at a single source.
+///
+/// The distances and predecessors are kept in node maps `DM` and `PM`;
+/// [`bellman_ford`] fills the graph's own [`Graph::Map`], and
+/// [`NodeIndexer::shortest_paths`](crate::NodeIndexer::shortest_paths)
+/// produces `HashMap`s.
+///
+/// # Example
+///
+/// ```rust
+/// use digraphx_rs::{bellman_ford, graph_from_edges};
+///
+/// let graph = graph_from_edges(&[('a', 'b', 4), ('b', 'c', 3), ('a', 'c', 10)]);
+/// let paths = bellman_ford(&graph, 'a', |w| *w).unwrap();
+/// assert_eq!(paths.distance('c'), Some(7));
+/// assert_eq!(paths.path_to('c'), Some(vec!['a', 'b', 'c']));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPaths<N, E, D, DM = HashMap<N, D>, PM = HashMap<N, (N, E)>> {
+    /// The source node.
+    pub source: N,
+    /// Distance from the source to every reachable node.
+    pub distances: DM,
+    /// Predecessor tree: each reachable node except the source maps to the
+    /// tail and payload of its incoming tree arc.
+    pub predecessors: PM,
+    _marker: PhantomData<(E, D)>,
+}
+
+impl<N, E, D, DM, PM> ShortestPaths<N, E, D, DM, PM> {
+    /// Bundle a source with its distance and predecessor maps.
+    pub fn new(source: N, distances: DM, predecessors: PM) -> Self {
+        ShortestPaths {
+            source,
+            distances,
+            predecessors,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<N, E, D, DM, PM> ShortestPaths<N, E, D, DM, PM>
+where
+    N: Copy + Eq,
+    D: Copy,
+    DM: NodeMap<N, D>,
+    PM: NodeMap<N, (N, E)>,
+{
+    /// Return the shortest distance to `target`, or `None` if it is
+    /// unreachable.
+    pub fn distance(&self, target: N) -> Option<D> {
+        self.distances.get(target).copied()
+    }
+
+    /// Reconstruct the shortest path from the source to `target`.
+    ///
+    /// Returns the nodes in path order (starting with the source), or `None`
+    /// if `target` is unreachable.
+    pub fn path_to(&self, target: N) -> Option<Vec<N>> {
+        if !self.distances.contains_node(target) {
+            return None;
+        }
+        let mut path = vec![target];
+        let mut vtx = target;
+        while vtx != self.source {
+            let &(u, _) = self.predecessors.get(vtx)?;
+            path.push(u);
+            vtx = u;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Error returned by [`bellman_ford`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ShortestPathError<N, E, D> {
+    /// A negative cycle is reachable from the source, so shortest distances
+    /// are unbounded.
+    NegativeCycle(Cycle<N, E, D>),
+    /// The predecessor tree holds a chain that does not close into a cycle,
+    /// reported as [`Error::InconsistentGraph`].
+    Graph(Error<N>),
+}
+
+impl<N: fmt::Debug, E, D> fmt::Display for ShortestPathError<N, E, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortestPathError::NegativeCycle(cycle) => write!(
+                f,
+                "negative cycle of length {} reachable from the source",
+                cycle.len()
+            ),
+            ShortestPathError::Graph(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<N: fmt::Debug, E: fmt::Debug, D: fmt::Debug> std::error::Error for ShortestPathError<N, E, D> {}
+
+/// Result of [`bellman_ford`] on a graph `G` with distances in `D`.
+pub type BellmanFordResult<G, D> = Result<
+    ShortestPaths<
+        <G as Graph>::Node,
+        <G as Graph>::Edge,
+        D,
+        <G as Graph>::Map<D>,
+        <G as Graph>::Map<(<G as Graph>::Node, <G as Graph>::Edge)>,
+    >,
+    ShortestPathError<<G as Graph>::Node, <G as Graph>::Edge, D>,
+>;
+
+/// Compute shortest paths from `source` with the Bellman–Ford algorithm.
+///
+/// Relaxes all edges until no distance changes.  After $n$ passes, where
+/// $n$ is the number of nodes, the predecessor tree is also searched for a
+/// cycle after every pass; such a cycle is negative and reachable from
+/// `source`, and is returned as [`ShortestPathError::NegativeCycle`].  A
+/// predecessor chain that does not close into a cycle, which only an
+/// inconsistent [`Graph`] implementation can produce, is returned as
+/// [`ShortestPathError::Graph`].  Nodes that only appear as
+/// neighbors may be missing from `num_nodes`, so the extra passes do not
+/// rely on that count being exact.
+///
+/// Complexity: $O(nm)$ time when no negative cycle is reachable, $O(n)$
+/// extra space.
+///
+/// # Example
+///
+/// ```rust
+/// use digraphx_rs::{bellman_ford, graph_from_edges, ShortestPathError};
+///
+/// let graph = graph_from_edges(&[(0, 1, 1), (1, 2, 1), (2, 1, -3)]);
+/// let Err(ShortestPathError::NegativeCycle(cycle)) = bellman_ford(&graph, 0, |w| *w) else {
+///     panic!("expected a negative cycle");
+/// };
+/// assert_eq!(cycle.weight, -2);
+/// ```
+pub fn bellman_ford<G, D, F>(graph: &G, source: G::Node, get_weight: F) -> BellmanFordResult<G, D>
+where
+    G: Graph,
+    D: Add<Output = D> + PartialOrd + Copy + Zero,
+    F: Fn(&G::Edge) -> D,
+{
+    let mut distances: G::Map<D> = graph.node_map();
+    let mut predecessors: G::Map<(G::Node, G::Edge)> = graph.node_map();
+    distances.set(source, D::zero());
+
+    let n = graph.num_nodes();
+    for pass in 1.. {
+        let mut changed = false;
+        for u in graph.nodes() {
+            let Some(&du) = distances.get(u) else {
+                continue;
+            };
+            for (v, e) in graph.neighbors(u) {
+                let distance = du + get_weight(&e);
+                if distances.get(v).map_or(true, |&dv| dv > distance) {
+                    distances.set(v, distance);
+                    predecessors.set(v, (u, e));
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+        // Past `n` passes a distance keeps falling only along a negative
+        // cycle, which then shows up in the predecessor tree.
+        if pass >= n {
+            if let Some(&vtx) = find_cycles(graph, &predecessors).first() {
+                return Err(
+                    match Cycle::try_from_pred(vtx, &predecessors, &get_weight) {
+                        Ok(cycle) => ShortestPathError::NegativeCycle(cycle),
+                        Err(e) => ShortestPathError::Graph(e),
+                    },
+                );
+            }
+        }
+    }
+    Ok(ShortestPaths::new(source, distances, predecessors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_from_edges;
+    use crate::graph_from_edges_array;
+
+    #[test]
+    fn test_simple_paths() {
+        let graph = graph_from_edges(&[("a", "b", 4.0), ("b", "c", 3.0), ("a", "c", 10.0)]);
+        let paths = bellman_ford(&graph, "a", |w| *w).unwrap();
+        assert_eq!(paths.distance("a"), Some(0.0));
+        assert_eq!(paths.distance("b"), Some(4.0));
+        assert_eq!(paths.distance("c"), Some(7.0));
+        assert_eq!(paths.path_to("c"), Some(vec!["a", "b", "c"]));
+        assert_eq!(paths.path_to("a"), Some(vec!["a"]));
+    }
+
+    #[test]
+    fn test_negative_edges() {
+        let graph = graph_from_edges(&[(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2)]);
+        let paths = bellman_ford(&graph, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(1), Some(2));
+        assert_eq!(paths.distance(3), Some(4));
+        assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(paths.predecessors[&1].0, 2);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let graph = graph_from_edges(&[(0, 1, 1), (2, 0, 1)]);
+        let paths = bellman_ford(&graph, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(2), None);
+        assert_eq!(paths.path_to(2), None);
+        assert_eq!(paths.distances.len(), 2);
+    }
+
+    #[test]
+    fn test_negative_cycle_reachable() {
+        let graph = graph_from_edges(&[(0, 1, 1), (1, 2, 2), (2, 3, -1), (3, 1, -2)]);
+        let err = bellman_ford(&graph, 0, |w| *w).unwrap_err();
+        assert!(err.to_string().contains("negative cycle"));
+        let ShortestPathError::NegativeCycle(cycle) = err else {
+            panic!("expected a negative cycle");
+        };
+        assert_eq!(cycle.weight, -1);
+        let mut nodes = cycle.nodes.clone();
+        nodes.sort();
+        assert_eq!(nodes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_negative_cycle_unreachable() {
+        // the negative cycle 2 <-> 3 cannot be reached from 0
+        let graph = graph_from_edges(&[(0, 1, 1), (2, 3, 1), (3, 2, -5)]);
+        let paths = bellman_ford(&graph, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(1), Some(1));
+        assert_eq!(paths.distance(2), None);
+    }
+
+    #[test]
+    fn test_array_graph() {
+        let graph = graph_from_edges_array(&[(0, 1, 2i32), (1, 2, 2), (0, 2, 5)]);
+        let paths = bellman_ford(&graph, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(2), Some(4));
+    }
+
+    #[test]
+    fn test_sink_not_a_key() {
+        // node 1 is only a neighbor, so `num_nodes` counts a single node
+        let graph: HashMap<i32, HashMap<i32, i32>> = [(0, [(1, 1)].into())].into();
+        let paths = bellman_ford(&graph, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(1), Some(1));
+        assert_eq!(paths.path_to(1), Some(vec![0, 1]));
+
+        let graph: HashMap<i32, HashMap<i32, i32>> =
+            [(0, [(1, 1)].into()), (1, [(2, 1), (3, -1)].into())].into();
+        let paths = bellman_ford(&graph, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(2), Some(2));
+        assert_eq!(paths.distance(3), Some(0));
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph: HashMap<i32, HashMap<i32, i32>> = HashMap::new();
+        let paths = bellman_ford(&graph, 0, |w| *w).unwrap();
+        assert_eq!(paths.distance(0), Some(0));
+    }
+}
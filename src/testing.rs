@@ -0,0 +1,34 @@
+//! Helpers shared by the randomized unit tests.
+
+/// Small deterministic generator so the cross-checks need no extra crate.
+pub(crate) struct Lcg(pub(crate) u64);
+
+impl Lcg {
+    /// Return a number in `0..bound`.
+    pub(crate) fn next(&mut self, bound: u32) -> u32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound as u64) as u32
+    }
+}
+
+/// Edges of a random graph on `2 + rng.next(extra_nodes)` nodes, each with
+/// one to three arcs to random heads (self-loops and parallel arcs
+/// included), whose payloads are drawn by `payload`.
+pub(crate) fn random_edges<E>(
+    rng: &mut Lcg,
+    extra_nodes: u32,
+    mut payload: impl FnMut(&mut Lcg) -> E,
+) -> Vec<(u32, u32, E)> {
+    let n = 2 + rng.next(extra_nodes);
+    let mut edges = Vec::new();
+    for u in 0..n {
+        for _ in 0..1 + rng.next(3) {
+            let v = rng.next(n);
+            edges.push((u, v, payload(rng)));
+        }
+    }
+    edges
+}
@@ -0,0 +1,757 @@
+//! Lazy views of a graph.
+//!
+//! The adapters in this module implement [`Graph`] on top of another
+//! `Graph` without copying it: [`MapWeights`] transforms the edge payloads,
+//! [`FilterEdges`] and [`FilterNodes`] hide the edges and nodes rejected by a
+//! predicate, and [`InducedSubgraph`] keeps a given set of nodes.  Each view
+//! takes the graph by value, so a view can wrap a reference (`&G` is a
+//! `Graph`) or another view, and views compose freely.  When the wrapped
+//! graph is a [`ReverseGraph`], so is the view.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use digraphx_rs::views::{FilterEdges, MapWeights};
+//! use digraphx_rs::{graph_from_edges, NegCycleFinder};
+//!
+//! #[derive(Clone, Copy)]
+//! struct Arc {
+//!     cost: f64,
+//!     disabled: bool,
+//! }
+//!
+//! let arc = |cost| Arc { cost, disabled: false };
+//! let graph = graph_from_edges(&[
+//!     (0, 1, arc(1.0)),
+//!     (1, 0, arc(-2.0)),
+//!     (1, 2, Arc { cost: -5.0, disabled: true }),
+//!     (2, 0, arc(1.0)),
+//! ]);
+//!
+//! // The graph without disabled edges, with costs scaled by λ = 0.5
+//! let lambda = 0.5;
+//! let enabled = FilterEdges::new(&graph, |_, _, a: &Arc| !a.disabled);
+//! let scaled = MapWeights::new(enabled, |a: &Arc| a.cost * lambda);
+//!
+//! let mut ncf = NegCycleFinder::new(&scaled);
+//! let mut dist: HashMap<i32, f64> = HashMap::new();
+//! let cycle = ncf.howard(&mut dist, |w| *w).unwrap();
+//! assert_eq!(cycle.weight, -0.5);
+//! ```
+
+use std::iter::Copied;
+use std::slice::Iter;
+
+use crate::node_map::NodeMap;
+use crate::{Graph, ReverseGraph};
+
+// ---------------------------------------------------------------------------
+// MapWeights
+// ---------------------------------------------------------------------------
+
+/// A graph whose edge payloads are `f(&e)` for the edges `e` of `G`.
+///
+/// `f` is called every time an edge is visited, so it should be cheap.
+#[derive(Debug, Clone)]
+pub struct MapWeights<G, F> {
+    graph: G,
+    f: F,
+}
+
+impl<G, F> MapWeights<G, F> {
+    /// Create a view of `graph` with the edge payloads mapped by `f`.
+    pub fn new(graph: G, f: F) -> Self {
+        MapWeights { graph, f }
+    }
+
+    /// Return the wrapped graph.
+    #[inline]
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Consume the view and return the wrapped graph.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+}
+
+/// Iterator over the neighbors of a [`MapWeights`] view.
+#[derive(Debug, Clone)]
+pub struct MappedNeighbors<'a, I, F> {
+    iter: I,
+    f: &'a F,
+}
+
+impl<'a, N, E, W, I, F> Iterator for MappedNeighbors<'a, I, F>
+where
+    I: Iterator<Item = (N, E)>,
+    F: Fn(&E) -> W,
+{
+    type Item = (N, W);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(v, e)| (v, (self.f)(&e)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<G, F, W> Graph for MapWeights<G, F>
+where
+    G: Graph,
+    F: Fn(&G::Edge) -> W,
+    W: Copy,
+{
+    type Node = G::Node;
+    type Edge = W;
+    type Nodes<'a>
+        = G::Nodes<'a>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = MappedNeighbors<'a, G::Neighbors<'a>, F>
+    where
+        Self: 'a;
+    type Map<V> = G::Map<V>;
+
+    #[inline]
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.graph.nodes()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_> {
+        MappedNeighbors {
+            iter: self.graph.neighbors(node),
+            f: &self.f,
+        }
+    }
+
+    #[inline]
+    fn num_nodes(&self) -> usize {
+        self.graph.num_nodes()
+    }
+
+    #[inline]
+    fn node_map<V>(&self) -> Self::Map<V> {
+        self.graph.node_map()
+    }
+}
+
+impl<G, F, W> ReverseGraph for MapWeights<G, F>
+where
+    G: ReverseGraph,
+    F: Fn(&G::Edge) -> W,
+    W: Copy,
+{
+    type InNeighbors<'a>
+        = MappedNeighbors<'a, G::InNeighbors<'a>, F>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn in_neighbors(&self, node: Self::Node) -> Self::InNeighbors<'_> {
+        MappedNeighbors {
+            iter: self.graph.in_neighbors(node),
+            f: &self.f,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FilterEdges
+// ---------------------------------------------------------------------------
+
+/// A graph with the edges `(u, v, e)` of `G` for which `pred(u, v, &e)`
+/// holds.  All nodes are kept.
+#[derive(Debug, Clone)]
+pub struct FilterEdges<G, P> {
+    graph: G,
+    pred: P,
+}
+
+impl<G, P> FilterEdges<G, P> {
+    /// Create a view of `graph` keeping the edges accepted by `pred`, which
+    /// receives the source, the target and the payload of an edge.
+    pub fn new(graph: G, pred: P) -> Self {
+        FilterEdges { graph, pred }
+    }
+
+    /// Return the wrapped graph.
+    #[inline]
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Consume the view and return the wrapped graph.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+}
+
+/// Iterator over the neighbors of a [`FilterEdges`] view.
+#[derive(Debug, Clone)]
+pub struct FilteredEdges<'a, N, I, P> {
+    node: N,
+    incoming: bool,
+    iter: I,
+    pred: &'a P,
+}
+
+impl<'a, N, E, I, P> Iterator for FilteredEdges<'a, N, I, P>
+where
+    N: Copy,
+    I: Iterator<Item = (N, E)>,
+    P: Fn(N, N, &E) -> bool,
+{
+    type Item = (N, E);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, incoming, pred) = (self.node, self.incoming, self.pred);
+        self.iter.find(|(v, e)| {
+            if incoming {
+                pred(*v, node, e)
+            } else {
+                pred(node, *v, e)
+            }
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<G, P> Graph for FilterEdges<G, P>
+where
+    G: Graph,
+    P: Fn(G::Node, G::Node, &G::Edge) -> bool,
+{
+    type Node = G::Node;
+    type Edge = G::Edge;
+    type Nodes<'a>
+        = G::Nodes<'a>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = FilteredEdges<'a, G::Node, G::Neighbors<'a>, P>
+    where
+        Self: 'a;
+    type Map<V> = G::Map<V>;
+
+    #[inline]
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.graph.nodes()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_> {
+        FilteredEdges {
+            node,
+            incoming: false,
+            iter: self.graph.neighbors(node),
+            pred: &self.pred,
+        }
+    }
+
+    #[inline]
+    fn num_nodes(&self) -> usize {
+        self.graph.num_nodes()
+    }
+
+    #[inline]
+    fn node_map<V>(&self) -> Self::Map<V> {
+        self.graph.node_map()
+    }
+}
+
+impl<G, P> ReverseGraph for FilterEdges<G, P>
+where
+    G: ReverseGraph,
+    P: Fn(G::Node, G::Node, &G::Edge) -> bool,
+{
+    type InNeighbors<'a>
+        = FilteredEdges<'a, G::Node, G::InNeighbors<'a>, P>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn in_neighbors(&self, node: Self::Node) -> Self::InNeighbors<'_> {
+        FilteredEdges {
+            node,
+            incoming: true,
+            iter: self.graph.in_neighbors(node),
+            pred: &self.pred,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FilterNodes
+// ---------------------------------------------------------------------------
+
+/// A graph with the nodes of `G` for which `pred(node)` holds and the edges
+/// between them.
+///
+/// `pred` is evaluated lazily, so [`num_nodes`](Graph::num_nodes) walks all
+/// nodes of `G`; use [`InducedSubgraph`] to fix the node set once.
+#[derive(Debug, Clone)]
+pub struct FilterNodes<G, P> {
+    graph: G,
+    pred: P,
+}
+
+impl<G, P> FilterNodes<G, P> {
+    /// Create a view of `graph` keeping the nodes accepted by `pred`.
+    pub fn new(graph: G, pred: P) -> Self {
+        FilterNodes { graph, pred }
+    }
+
+    /// Return the wrapped graph.
+    #[inline]
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Consume the view and return the wrapped graph.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+}
+
+/// Iterator over the nodes of a [`FilterNodes`] view.
+#[derive(Debug, Clone)]
+pub struct FilteredNodes<'a, I, P> {
+    iter: I,
+    pred: &'a P,
+}
+
+impl<'a, N, I, P> Iterator for FilteredNodes<'a, I, P>
+where
+    N: Copy,
+    I: Iterator<Item = N>,
+    P: Fn(N) -> bool,
+{
+    type Item = N;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pred = self.pred;
+        self.iter.find(|&u| pred(u))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Iterator over the neighbors of a [`FilterNodes`] view.
+///
+/// Yields nothing for a node the predicate rejects.
+#[derive(Debug, Clone)]
+pub struct NodeFilteredNeighbors<'a, I, P> {
+    iter: Option<I>,
+    pred: &'a P,
+}
+
+impl<'a, N, E, I, P> Iterator for NodeFilteredNeighbors<'a, I, P>
+where
+    N: Copy,
+    I: Iterator<Item = (N, E)>,
+    P: Fn(N) -> bool,
+{
+    type Item = (N, E);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pred = self.pred;
+        self.iter.as_mut()?.find(|&(v, _)| pred(v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.as_ref().map_or(Some(0), |it| it.size_hint().1))
+    }
+}
+
+impl<G, P> FilterNodes<G, P>
+where
+    G: Graph,
+    P: Fn(G::Node) -> bool,
+{
+    fn filtered<I>(
+        &self,
+        node: G::Node,
+        iter: impl FnOnce() -> I,
+    ) -> NodeFilteredNeighbors<'_, I, P> {
+        NodeFilteredNeighbors {
+            iter: (self.pred)(node).then(iter),
+            pred: &self.pred,
+        }
+    }
+}
+
+impl<G, P> Graph for FilterNodes<G, P>
+where
+    G: Graph,
+    P: Fn(G::Node) -> bool,
+{
+    type Node = G::Node;
+    type Edge = G::Edge;
+    type Nodes<'a>
+        = FilteredNodes<'a, G::Nodes<'a>, P>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = NodeFilteredNeighbors<'a, G::Neighbors<'a>, P>
+    where
+        Self: 'a;
+    type Map<V> = G::Map<V>;
+
+    #[inline]
+    fn nodes(&self) -> Self::Nodes<'_> {
+        FilteredNodes {
+            iter: self.graph.nodes(),
+            pred: &self.pred,
+        }
+    }
+
+    #[inline]
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_> {
+        self.filtered(node, || self.graph.neighbors(node))
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.nodes().count()
+    }
+
+    #[inline]
+    fn node_map<V>(&self) -> Self::Map<V> {
+        self.graph.node_map()
+    }
+}
+
+impl<G, P> ReverseGraph for FilterNodes<G, P>
+where
+    G: ReverseGraph,
+    P: Fn(G::Node) -> bool,
+{
+    type InNeighbors<'a>
+        = NodeFilteredNeighbors<'a, G::InNeighbors<'a>, P>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn in_neighbors(&self, node: Self::Node) -> Self::InNeighbors<'_> {
+        self.filtered(node, || self.graph.in_neighbors(node))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// InducedSubgraph
+// ---------------------------------------------------------------------------
+
+/// The subgraph of `G` induced by a set of nodes: those nodes and the edges
+/// of `G` between them.
+///
+/// The node set is stored in a `G::Map<()>`, so membership tests cost what
+/// a lookup in the graph's own node map costs.
+pub struct InducedSubgraph<G: Graph> {
+    graph: G,
+    nodes: Vec<G::Node>,
+    members: G::Map<()>,
+}
+
+impl<G: Graph> InducedSubgraph<G> {
+    /// Create the subgraph of `graph` induced by `nodes`.
+    ///
+    /// Duplicates are ignored; the nodes are listed in their first order of
+    /// appearance.  The nodes are expected to belong to `graph`.
+    pub fn new(graph: G, nodes: impl IntoIterator<Item = G::Node>) -> Self {
+        let mut members: G::Map<()> = graph.node_map();
+        let nodes = nodes
+            .into_iter()
+            .filter(|&u| {
+                let fresh = !members.contains_node(u);
+                members.set(u, ());
+                fresh
+            })
+            .collect();
+        InducedSubgraph {
+            graph,
+            nodes,
+            members,
+        }
+    }
+
+    /// Return `true` if `node` belongs to the subgraph.
+    #[inline]
+    pub fn contains(&self, node: G::Node) -> bool {
+        self.members.contains_node(node)
+    }
+
+    /// Return the wrapped graph.
+    #[inline]
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Consume the view and return the wrapped graph.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+
+    fn induced<I>(
+        &self,
+        node: G::Node,
+        iter: impl FnOnce() -> I,
+    ) -> InducedNeighbors<'_, I, G::Map<()>> {
+        InducedNeighbors {
+            iter: self.contains(node).then(iter),
+            members: &self.members,
+        }
+    }
+}
+
+/// Iterator over the neighbors of an [`InducedSubgraph`].
+///
+/// Yields nothing for a node outside the subgraph.
+#[derive(Debug, Clone)]
+pub struct InducedNeighbors<'a, I, M> {
+    iter: Option<I>,
+    members: &'a M,
+}
+
+impl<'a, N, E, I, M> Iterator for InducedNeighbors<'a, I, M>
+where
+    N: Copy,
+    I: Iterator<Item = (N, E)>,
+    M: NodeMap<N, ()>,
+{
+    type Item = (N, E);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let members = self.members;
+        self.iter.as_mut()?.find(|&(v, _)| members.contains_node(v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.as_ref().map_or(Some(0), |it| it.size_hint().1))
+    }
+}
+
+impl<G: Graph> Graph for InducedSubgraph<G> {
+    type Node = G::Node;
+    type Edge = G::Edge;
+    type Nodes<'a>
+        = Copied<Iter<'a, G::Node>>
+    where
+        Self: 'a;
+    type Neighbors<'a>
+        = InducedNeighbors<'a, G::Neighbors<'a>, G::Map<()>>
+    where
+        Self: 'a;
+    type Map<V> = G::Map<V>;
+
+    #[inline]
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.nodes.iter().copied()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_> {
+        self.induced(node, || self.graph.neighbors(node))
+    }
+
+    #[inline]
+    fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn node_map<V>(&self) -> Self::Map<V> {
+        self.graph.node_map()
+    }
+}
+
+impl<G: ReverseGraph> ReverseGraph for InducedSubgraph<G> {
+    type InNeighbors<'a>
+        = InducedNeighbors<'a, G::InNeighbors<'a>, G::Map<()>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn in_neighbors(&self, node: Self::Node) -> Self::InNeighbors<'_> {
+        self.induced(node, || self.graph.in_neighbors(node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parametric::{MaxParametricSolver, MinMeanCycle};
+    use crate::{graph_from_edges, NegCycleFinder, Reversed};
+    use std::collections::HashMap;
+
+    fn sorted<T: Ord>(iter: impl Iterator<Item = T>) -> Vec<T> {
+        let mut items: Vec<T> = iter.collect();
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn test_map_weights() {
+        let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1), (2, -2)], vec![(0, 3)], vec![]];
+        let scaled = MapWeights::new(&g, |w: &i32| f64::from(*w) * 0.5);
+        assert_eq!(scaled.num_nodes(), 3);
+        assert_eq!(scaled.nodes().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(
+            scaled.neighbors(0).collect::<Vec<_>>(),
+            vec![(1, 0.5), (2, -1.0)]
+        );
+        assert_eq!(scaled.neighbors(0).size_hint(), (2, Some(2)));
+        assert!(scaled.neighbors(2).next().is_none());
+        assert_eq!(scaled.inner().len(), 3);
+    }
+
+    #[test]
+    fn test_map_weights_scaled_cycle() {
+        // Costs minus λ: the cycle 0 -> 1 -> 0 of mean 2 turns negative for λ > 2
+        let g = graph_from_edges(&[(0, 1, 1.0), (1, 0, 3.0), (1, 2, 5.0), (2, 1, 5.0)]);
+        for (lambda, negative) in [(1.5, false), (2.5, true)] {
+            let shifted = MapWeights::new(&g, move |w: &f64| w - lambda);
+            let mut dist: HashMap<i32, f64> = HashMap::new();
+            let cycle = NegCycleFinder::new(&shifted).howard(&mut dist, |w| *w);
+            assert_eq!(cycle.is_some(), negative);
+        }
+    }
+
+    #[test]
+    fn test_filter_edges() {
+        let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1), (2, -2)], vec![(0, 3)], vec![(0, 4)]];
+        let positive = FilterEdges::new(&g, |_, _, w: &i32| *w > 0);
+        assert_eq!(positive.num_nodes(), 3);
+        assert_eq!(positive.neighbors(0).collect::<Vec<_>>(), vec![(1, 1)]);
+        assert_eq!(positive.neighbors(0).size_hint(), (0, Some(2)));
+
+        let no_loops_to_0 = FilterEdges::new(&g, |_, v, _: &i32| v != 0);
+        assert!(no_loops_to_0.neighbors(1).next().is_none());
+        assert_eq!(no_loops_to_0.neighbors(0).count(), 2);
+    }
+
+    #[test]
+    fn test_filter_edges_disables_cycle() {
+        let g = graph_from_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, -3), (2, 1, 5)]);
+        let without = FilterEdges::new(&g, |u, v, _: &i32| (u, v) != (2, 0));
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        assert!(NegCycleFinder::new(&g).howard(&mut dist, |w| *w).is_some());
+        let mut dist: HashMap<i32, i32> = HashMap::new();
+        assert!(NegCycleFinder::new(&without)
+            .howard(&mut dist, |w| *w)
+            .is_none());
+    }
+
+    #[test]
+    fn test_filter_nodes() {
+        let g: Vec<Vec<(usize, i32)>> = vec![
+            vec![(1, 1), (2, 2)],
+            vec![(2, 3), (3, 4)],
+            vec![(0, 5)],
+            vec![(0, 6)],
+        ];
+        let odd_free = FilterNodes::new(&g, |u: usize| u % 2 == 0);
+        assert_eq!(odd_free.nodes().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(odd_free.num_nodes(), 2);
+        assert_eq!(odd_free.neighbors(0).collect::<Vec<_>>(), vec![(2, 2)]);
+        assert_eq!(odd_free.neighbors(2).collect::<Vec<_>>(), vec![(0, 5)]);
+        assert!(odd_free.neighbors(1).next().is_none());
+        assert_eq!(odd_free.neighbors(1).size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_induced_subgraph() {
+        let g = graph_from_edges(&[("a", "b", 1), ("b", "c", 2), ("c", "a", 3), ("a", "c", 4)]);
+        let sub = InducedSubgraph::new(&g, ["c", "a", "c"]);
+        assert_eq!(sub.num_nodes(), 2);
+        assert_eq!(sub.nodes().collect::<Vec<_>>(), vec!["c", "a"]);
+        assert!(sub.contains("a"));
+        assert!(!sub.contains("b"));
+        assert_eq!(sub.neighbors("a").collect::<Vec<_>>(), vec![("c", 4)]);
+        assert_eq!(sub.neighbors("c").collect::<Vec<_>>(), vec![("a", 3)]);
+        assert!(sub.neighbors("b").next().is_none());
+        assert_eq!(sub.into_inner().num_nodes(), 3);
+    }
+
+    #[test]
+    fn test_induced_subgraph_parametric() {
+        // Minimum mean cycle of the whole graph is 1.5, of {1, 2} it is 4
+        let g: Vec<Vec<(usize, f64)>> =
+            vec![vec![(1, 1.0)], vec![(0, 2.0), (2, 4.0)], vec![(1, 4.0)]];
+        let sub = InducedSubgraph::new(&g, [1, 2]);
+        let result = MaxParametricSolver::new(&sub, MinMeanCycle).run(vec![0.0; 3], 10.0);
+        assert_eq!(result.ratio, 4.0);
+    }
+
+    #[test]
+    fn test_views_compose() {
+        let g: Vec<Vec<(usize, i32)>> = vec![
+            vec![(1, 1), (3, 7)],
+            vec![(2, 2), (0, -1)],
+            vec![(0, 3), (3, 8)],
+            vec![(0, 9)],
+        ];
+        let view = MapWeights::new(
+            FilterEdges::new(
+                InducedSubgraph::new(FilterNodes::new(&g, |u: usize| u != 2), [0, 1, 3]),
+                |_, _, w: &i32| *w != 7,
+            ),
+            |w: &i32| 10 * w,
+        );
+        assert_eq!(view.num_nodes(), 3);
+        assert_eq!(view.neighbors(0).collect::<Vec<_>>(), vec![(1, 10)]);
+        assert_eq!(view.neighbors(1).collect::<Vec<_>>(), vec![(0, -10)]);
+        assert!(view.neighbors(2).next().is_none());
+        assert_eq!(view.neighbors(3).collect::<Vec<_>>(), vec![(0, 90)]);
+
+        let mut dist = vec![0; 4];
+        let cycle = NegCycleFinder::new(&view).howard(&mut dist, |w| *w);
+        assert!(cycle.is_none());
+    }
+
+    #[test]
+    fn test_views_reverse() {
+        let g: Vec<Vec<(usize, i32)>> = vec![vec![(1, 1), (2, 2)], vec![(2, 3)], vec![(0, 4)]];
+        let r = Reversed::new(&g);
+
+        let view = MapWeights::new(
+            FilterEdges::new(&r, |u, v, _: &i32| (u, v) != (1, 0)),
+            |w: &i32| -w,
+        );
+        // Incoming edges of the reversed graph are the edges of `g`, and the
+        // predicate sees them in the reversed direction
+        assert_eq!(view.in_neighbors(0).collect::<Vec<_>>(), vec![(2, -2)]);
+        assert_eq!(view.in_neighbors(1).collect::<Vec<_>>(), vec![(2, -3)]);
+        assert_eq!(view.neighbors(0).collect::<Vec<_>>(), vec![(2, -4)]);
+
+        let sub = InducedSubgraph::new(FilterNodes::new(&r, |u: usize| u != 1), [0, 2]);
+        assert_eq!(sub.num_nodes(), 2);
+        assert_eq!(sorted(sub.in_neighbors(0)), vec![(2, 2)]);
+        assert!(sub.in_neighbors(1).next().is_none());
+        assert_eq!(sorted(sub.neighbors(2)), vec![(0, 2)]);
+    }
+}
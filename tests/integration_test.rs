@@ -58,10 +58,9 @@ fn test_parametric_solver_integration() {
         (2, 0, 1),
     ]);
     let mut solver = MaxParametricSolver::new(&graph, MinCycle);
-    let mut dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
-    let mut ratio = 100i32;
-    solver.run(&mut dist, &mut ratio);
-    assert_eq!(ratio, 1);
+    let dist: HashMap<i32, i32> = [(0, 0), (1, 0), (2, 0)].into();
+    let result = solver.run(dist, 100i32);
+    assert_eq!(result.ratio, 1);
 }
 
 #[test]
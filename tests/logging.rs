@@ -0,0 +1,30 @@
+//! Global logger state around `capture_logs`.
+//!
+//! Kept in its own test binary: the maximum level is process-wide, and the
+//! captures of the unit tests running in parallel would change it.
+#![cfg(feature = "std")]
+
+use digraphx_rs::logging::{capture_logs, try_init_logger_with_filter};
+use log::LevelFilter;
+
+#[test]
+fn test_capture_logs_restores_max_level() {
+    let (level, _) = capture_logs(log::max_level).unwrap();
+    assert_eq!(level, LevelFilter::Trace);
+    assert_eq!(log::max_level(), LevelFilter::Off);
+
+    try_init_logger_with_filter("warn").unwrap();
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+    let (_, records) = capture_logs(|| {
+        assert_eq!(log::max_level(), LevelFilter::Trace);
+        let _ = capture_logs(|| log::trace!("inner"));
+        assert_eq!(log::max_level(), LevelFilter::Trace);
+        log::debug!("outer");
+    })
+    .unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+
+    let _ = std::panic::catch_unwind(|| capture_logs(|| panic!("boom")));
+    assert_eq!(log::max_level(), LevelFilter::Warn);
+}